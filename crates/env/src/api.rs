@@ -253,6 +253,35 @@ pub fn clear_contract_storage(key: &Key) {
     })
 }
 
+/// Returns the value stored under the given key in the runtime storage if any.
+///
+/// # Note
+///
+/// - This allows contracts to read the state of runtime modules such as the
+///   balances of the `balances` pallet. The given key is the raw storage key
+///   as used by the runtime which is usually derived via the TwoX hashes
+///   [`Twox128`](`crate::hash::Twox128`) of the pallet and storage item names.
+/// - The value is decoded as `R` which must be the type the runtime stores
+///   under the key.
+/// - On-chain this requires the chain to provide the `seal_get_runtime_storage`
+///   host function. Off-chain the runtime storage can be set via
+///   [`test::set_runtime_storage`](`crate::test::set_runtime_storage`).
+/// - This is only available with the `ink-unstable-runtime-storage` feature
+///   since the contracts pallet does not provide this host function, yet.
+///
+/// # Errors
+///
+/// - If the decoding of the typed value failed
+#[cfg(feature = "ink-unstable-runtime-storage")]
+pub fn get_runtime_storage<R>(key: &[u8]) -> Result<Option<R>>
+where
+    R: scale::Decode,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        EnvBackend::get_runtime_storage::<R>(instance, key)
+    })
+}
+
 /// Invokes a contract message.
 ///
 /// # Note
@@ -275,9 +304,7 @@ where
     T: Environment,
     Args: scale::Encode,
 {
-    <EnvInstance as OnInstance>::on_instance(|instance| {
-        TypedEnvBackend::invoke_contract::<T, Args>(instance, params)
-    })
+    crate::engine::invoke_contract::<T, Args>(params)
 }
 
 /// Evaluates a contract message and returns its result.
@@ -302,9 +329,7 @@ where
     Args: scale::Encode,
     R: scale::Decode,
 {
-    <EnvInstance as OnInstance>::on_instance(|instance| {
-        TypedEnvBackend::eval_contract::<T, Args, R>(instance, params)
-    })
+    crate::engine::eval_contract::<T, Args, R>(params)
 }
 
 /// Instantiates another contract.
@@ -405,6 +430,11 @@ pub fn restore_contract<T>(
 /// This function never returns. Either the termination was successful and the
 /// execution of the destroyed contract is halted. Or it failed during the termination
 /// which is considered fatal and results in a trap + rollback.
+///
+/// Since the off-chain environment cannot halt the execution it panics after
+/// transferring the remaining balance and removing the contract account together
+/// with its storage instead. The beneficiary can be queried
+/// via [`crate::test::recorded_termination_beneficiary`].
 pub fn terminate_contract<T>(beneficiary: T::AccountId) -> !
 where
     T: Environment,
@@ -422,9 +452,11 @@ where
 /// contract call or invoke a runtime function that performs the
 /// transaction.
 ///
-/// # Panics
+/// # Errors
 ///
-/// If the contract doesn't have sufficient funds.
+/// - If the contract doesn't have sufficient funds.
+/// - If the transfer would bring the contract's balance below the
+///   subsistence threshold.
 pub fn transfer<T>(destination: T::AccountId, value: T::Balance) -> Result<()>
 where
     T: Environment,
@@ -491,7 +523,29 @@ where
 ///
 /// # Note
 ///
-/// This function  stops the execution of the contract immediately.
+/// - This function  stops the execution of the contract immediately.
+/// - In the off-chain environment this panics after recording the return value
+///   which can then be queried using
+///   [`test::recorded_return_value`](`crate::test::recorded_return_value`).
+///
+/// # Example
+///
+/// ```
+/// # use ink_env::ReturnFlags;
+/// # ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+/// let output = (42_u32, true); // Implements `scale::Encode`
+/// let result = std::panic::catch_unwind(|| {
+///     ink_env::return_value(ReturnFlags::default(), &output)
+/// });
+/// // Off-chain the returned value is recorded instead of ending the process.
+/// assert!(result.is_err());
+/// assert_eq!(
+///     ink_env::test::recorded_return_value(),
+///     Some(scale::Encode::encode(&output)),
+/// );
+/// # Ok(())
+/// # }).unwrap();
+/// ```
 pub fn return_value<R>(return_flags: ReturnFlags, return_value: &R) -> !
 where
     R: scale::Encode,
@@ -501,12 +555,34 @@ where
     })
 }
 
+/// Reverts the execution of the contract and returns the revert data to the caller.
+///
+/// # Note
+///
+/// - This function stops the execution of the contract immediately.
+/// - All changes to the contract storage made during the execution are rolled back.
+/// - In the off-chain environment this restores the contract storage as it was
+///   before the execution and panics after recording the revert data which can
+///   then be queried using [`test::recorded_revert_data`](`crate::test::recorded_revert_data`).
+pub fn revert<R>(revert_data: &R) -> !
+where
+    R: scale::Encode,
+{
+    return_value::<R>(ReturnFlags::default().set_reverted(true), revert_data)
+}
+
 /// Returns a random hash seed.
 ///
 /// # Note
 ///
 /// - The subject buffer can be used to further randomize the hash.
 /// - Within the same execution returns the same random hash for the same subject.
+/// - The randomness is derived from recent blocks and therefore only weakly
+///   unpredictable. Block authors can influence it to some extent so it must
+///   not be relied upon where much value is at stake.
+/// - In the off-chain environment the random hash is derived from the entropy
+///   of the current block which can be set via
+///   [`test::set_block_entropy`](`crate::test::set_block_entropy`).
 ///
 /// # Errors
 ///
@@ -521,6 +597,11 @@ where
 }
 
 /// Prints the given contents to the environmental log.
+///
+/// # Note
+///
+/// In the off-chain environment the printed contents are recorded and can be
+/// inspected via [`test::recorded_printlns`](`crate::test::recorded_printlns`).
 pub fn debug_println(content: &str) {
     <EnvInstance as OnInstance>::on_instance(|instance| {
         EnvBackend::println(instance, content)
@@ -561,3 +642,31 @@ where
         instance.hash_encoded::<H, T>(input, output)
     })
 }
+
+/// Recovers the compressed ECDSA public key for the given signature and message hash.
+///
+/// The signature consists of the 32 bytes `r` and `s` of the secp256k1 signature
+/// followed by its recovery id. The recovery id is either within `0..=3` or
+/// within `27..=30` as used by Ethereum. Upon success the 33 bytes compressed
+/// public key of the signer is written into `output`.
+///
+/// # Note
+///
+/// - On-chain this requires the chain to provide the `seal_ecdsa_recover` host
+///   function.
+/// - This is only available with the `ink-unstable-ecdsa` feature since the
+///   contracts pallet does not provide this host function, yet.
+///
+/// # Errors
+///
+/// If the public key cannot be recovered from the signature.
+#[cfg(feature = "ink-unstable-ecdsa")]
+pub fn ecdsa_recover(
+    signature: &[u8; 65],
+    message_hash: &[u8; 32],
+    output: &mut [u8; 33],
+) -> Result<()> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.ecdsa_recover(signature, message_hash, output)
+    })
+}
@@ -13,11 +13,7 @@
 // limitations under the License.
 
 use crate::{
-    call::{
-        utils::ReturnType,
-        CallParams,
-        CreateParams,
-    },
+    call::CreateParams,
     hash::{
         CryptoHash,
         HashOutput,
@@ -43,13 +39,24 @@ impl ReturnFlags {
     /// Sets the bit to indicate that the execution is going to be reverted.
     pub fn set_reverted(mut self, has_reverted: bool) -> Self {
         match has_reverted {
-            true => self.value |= has_reverted as u32,
-            false => self.value &= !has_reverted as u32,
+            true => self.value |= 1,
+            false => self.value &= !1,
         }
         self
     }
 
+    /// Returns `true` if the execution is going to be reverted.
+    pub fn is_reverted(&self) -> bool {
+        self.value & 1 != 0
+    }
+
     /// Returns the underlying `u32` representation.
+    #[cfg(
+        // The off-chain environment does not hand the flags over to an executor
+        // so we do not have to provide this getter in case of off-chain
+        // environment compilation.
+        any(all(not(feature = "std"), target_arch = "wasm32"), test)
+    )]
     pub(crate) fn into_u32(self) -> u32 {
         self.value
     }
@@ -74,6 +81,16 @@ pub trait EnvBackend {
     /// Clears the contract's storage key entry.
     fn clear_contract_storage(&mut self, key: &Key);
 
+    /// Returns the value stored under the given key in the runtime storage if any.
+    ///
+    /// # Errors
+    ///
+    /// - If the decoding of the typed value failed
+    #[cfg(feature = "ink-unstable-runtime-storage")]
+    fn get_runtime_storage<R>(&mut self, key: &[u8]) -> Result<Option<R>>
+    where
+        R: scale::Decode;
+
     /// Returns the execution input to the executed contract and decodes it as `T`.
     ///
     /// # Note
@@ -128,6 +145,19 @@ pub trait EnvBackend {
         H: CryptoHash,
         T: scale::Encode;
 
+    /// Recovers the compressed ECDSA public key for the given signature and message hash.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_env::ecdsa_recover`]
+    #[cfg(feature = "ink-unstable-ecdsa")]
+    fn ecdsa_recover(
+        &mut self,
+        signature: &[u8; 65],
+        message_hash: &[u8; 32],
+        output: &mut [u8; 33],
+    ) -> Result<()>;
+
     /// Calls the chain extension with the given ID and inputs.
     ///
     /// Returns the output of the chain extension of the specified type.
@@ -243,33 +273,6 @@ pub trait TypedEnvBackend: EnvBackend {
     where
         T: Environment;
 
-    /// Invokes a contract message.
-    ///
-    /// # Note
-    ///
-    /// For more details visit: [`ink_env::invoke_contract`]
-    fn invoke_contract<T, Args>(
-        &mut self,
-        call_data: &CallParams<T, Args, ()>,
-    ) -> Result<()>
-    where
-        T: Environment,
-        Args: scale::Encode;
-
-    /// Evaluates a contract message and returns its result.
-    ///
-    /// # Note
-    ///
-    /// For more details visit: [`ink_env::eval_contract`]
-    fn eval_contract<T, Args, R>(
-        &mut self,
-        call_data: &CallParams<T, Args, ReturnType<R>>,
-    ) -> Result<R>
-    where
-        T: Environment,
-        Args: scale::Encode,
-        R: scale::Decode;
-
     /// Instantiates another contract.
     ///
     /// # Note
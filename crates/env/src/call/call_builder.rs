@@ -46,12 +46,6 @@ where
     exec_input: ExecutionInput<Args>,
 }
 
-#[cfg(
-    // We do not currently support cross-contract calling in the off-chain
-    // environment so we do not have to provide these getters in case of
-    // off-chain environment compilation.
-    all(not(feature = "std"), target_arch = "wasm32")
-)]
 impl<E, Args, R> CallParams<E, Args, R>
 where
     E: Environment,
@@ -62,12 +56,6 @@ where
         &self.callee
     }
 
-    /// Returns the chosen gas limit for the called contract execution.
-    #[inline]
-    pub(crate) fn gas_limit(&self) -> u64 {
-        self.gas_limit
-    }
-
     /// Returns the transferred value for the called contract.
     #[inline]
     pub(crate) fn transferred_value(&self) -> &E::Balance {
@@ -81,6 +69,22 @@ where
     }
 }
 
+#[cfg(
+    // The off-chain environment does not meter gas so we do not have to
+    // provide this getter in case of off-chain environment compilation.
+    all(not(feature = "std"), target_arch = "wasm32")
+)]
+impl<E, Args, R> CallParams<E, Args, R>
+where
+    E: Environment,
+{
+    /// Returns the chosen gas limit for the called contract execution.
+    #[inline]
+    pub(crate) fn gas_limit(&self) -> u64 {
+        self.gas_limit
+    }
+}
+
 impl<E, Args> CallParams<E, Args, ()>
 where
     E: Environment,
@@ -120,9 +124,9 @@ where
 ///
 /// # Example
 ///
-/// **Note:** The shown examples panic because there is currently no cross-calling
-///           support in the off-chain testing environment. However, this code
-///           should work fine in on-chain environments.
+/// **Note:** The shown examples panic because no contract is registered at the
+///           called account in the off-chain testing environment. However, this
+///           code should work fine in on-chain environments.
 ///
 /// ## Example 1: No Return Value
 ///
@@ -60,12 +60,6 @@ where
     return_type: ReturnType<R>,
 }
 
-#[cfg(
-    // We do not currently support cross-contract instantiation in the off-chain
-    // environment so we do not have to provide these getters in case of
-    // off-chain environment compilation.
-    all(not(feature = "std"), target_arch = "wasm32")
-)]
 impl<E, Args, R> CreateParams<E, Args, R>
 where
     E: Environment,
@@ -76,17 +70,28 @@ where
         &self.code_hash
     }
 
-    /// The gas limit for the contract instantiation.
-    #[inline]
-    pub(crate) fn gas_limit(&self) -> u64 {
-        self.gas_limit
-    }
-
     /// The endowment for the instantiated contract.
     #[inline]
     pub(crate) fn endowment(&self) -> &E::Balance {
         &self.endowment
     }
+}
+
+#[cfg(
+    // The off-chain environment does not meter gas nor execute constructors
+    // so we do not have to provide these getters in case of off-chain
+    // environment compilation.
+    all(not(feature = "std"), target_arch = "wasm32")
+)]
+impl<E, Args, R> CreateParams<E, Args, R>
+where
+    E: Environment,
+{
+    /// The gas limit for the contract instantiation.
+    #[inline]
+    pub(crate) fn gas_limit(&self) -> u64 {
+        self.gas_limit
+    }
 
     /// The raw encoded input data.
     #[inline]
@@ -164,9 +169,9 @@ where
 ///     .unwrap();
 /// ```
 ///
-/// **Note:** The shown example panics because there is currently no cross-calling
-///           support in the off-chain testing environment. However, this code
-///           should work fine in on-chain environments.
+/// **Note:** The shown example panics because the off-chain testing environment
+///           has not been initialized for it. However, this code should work
+///           fine in on-chain environments.
 #[allow(clippy::type_complexity)]
 pub fn build_create<E, R>() -> CreateBuilder<
     E,
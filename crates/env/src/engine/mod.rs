@@ -18,6 +18,8 @@ use crate::backend::{
 };
 use cfg_if::cfg_if;
 
+mod twox;
+
 pub trait OnInstance: EnvBackend + TypedEnvBackend {
     fn on_instance<F, R>(f: F) -> R
     where
@@ -27,10 +29,18 @@ pub trait OnInstance: EnvBackend + TypedEnvBackend {
 cfg_if! {
     if #[cfg(all(not(feature = "std"), target_arch = "wasm32"))] {
         mod on_chain;
-        pub use self::on_chain::EnvInstance;
+        pub use self::on_chain::{
+            eval_contract,
+            invoke_contract,
+            EnvInstance,
+        };
     } else if #[cfg(feature = "std")] {
         pub mod off_chain;
-        pub use self::off_chain::EnvInstance;
+        pub use self::off_chain::{
+            eval_contract,
+            invoke_contract,
+            EnvInstance,
+        };
         pub use self::off_chain::{
             AccountError,
             TypedEncodedError,
@@ -0,0 +1,86 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::CallData;
+use crate::{
+    Environment,
+    Error,
+    Result,
+};
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    rc::Rc,
+};
+
+/// A mocked contract that handles calls to its account.
+///
+/// Receives the call data of the call and returns the encoded output.
+///
+/// # Note
+///
+/// Shared so that the handler can be invoked after the borrow of the
+/// off-chain environment has been released.
+pub type ContractCallFn = Rc<RefCell<dyn FnMut(CallData) -> Vec<u8>>>;
+
+/// Handler for calls to other contracts.
+///
+/// Maps the encoded account IDs of mocked contracts to their call handlers.
+pub struct ContractCallHandler {
+    /// The currently registered mocked contracts.
+    registered: HashMap<Vec<u8>, ContractCallFn>,
+}
+
+impl ContractCallHandler {
+    /// Creates a new contract call handler.
+    ///
+    /// Initialized without any mocked contracts.
+    pub fn new() -> Self {
+        Self {
+            registered: HashMap::new(),
+        }
+    }
+
+    /// Resets the contract call handler to uninitialized state.
+    pub fn reset(&mut self) {
+        self.registered.clear()
+    }
+
+    /// Registers the call handler of the mocked contract at the given account.
+    ///
+    /// Replaces the call handler previously registered for the account if any.
+    pub fn register<T, F>(&mut self, callee: &T::AccountId, handler: F)
+    where
+        T: Environment,
+        F: FnMut(CallData) -> Vec<u8> + 'static,
+    {
+        self.registered
+            .insert(scale::Encode::encode(callee), Rc::new(RefCell::new(handler)));
+    }
+
+    /// Returns the call handler of the mocked contract at the given account.
+    ///
+    /// # Errors
+    ///
+    /// If no mocked contract is registered for the account.
+    pub fn handler<T>(&self, callee: &T::AccountId) -> Result<ContractCallFn>
+    where
+        T: Environment,
+    {
+        self.registered
+            .get(&scale::Encode::encode(callee))
+            .cloned()
+            .ok_or(Error::NotCallable)
+    }
+}
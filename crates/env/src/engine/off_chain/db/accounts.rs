@@ -24,7 +24,10 @@ use crate::{
     Environment,
     Error,
 };
-use core::cell::Cell;
+use core::cell::{
+    Cell,
+    RefCell,
+};
 use derive_more::From;
 use ink_prelude::collections::BTreeMap;
 use ink_primitives::Key;
@@ -145,6 +148,16 @@ impl AccountsDb {
         );
     }
 
+    /// Removes the account at the given account ID together with its storage.
+    ///
+    /// Returns the removed account if any.
+    pub fn remove_account<T>(&mut self, at: &T::AccountId) -> Option<Account>
+    where
+        T: Environment,
+    {
+        self.accounts.remove(&OffAccountId::new(at))
+    }
+
     /// Creates a new contract account.
     pub fn add_contract_account<T>(
         &mut self,
@@ -259,6 +272,25 @@ impl Account {
     pub fn get_storage_rw(&self) -> Result<(usize, usize)> {
         self.contract_or_err().map(|contract| contract.get_rw())
     }
+
+    /// Returns the number of reads and writes from and to the contract's storage
+    /// at the given key.
+    pub fn get_storage_rw_at(&self, at: &Key) -> Result<(usize, usize)> {
+        self.contract_or_err()
+            .map(|contract| contract.storage.get_rw_at(at))
+    }
+
+    /// Returns a snapshot of the entries of the contract storage.
+    pub fn storage_snapshot(&self) -> Result<StorageSnapshot> {
+        self.contract_or_err()
+            .map(|contract| contract.storage.snapshot())
+    }
+
+    /// Restores the entries of the contract storage from the given snapshot.
+    pub fn restore_storage(&mut self, snapshot: StorageSnapshot) -> Result<()> {
+        self.contract_or_err_mut()
+            .map(|contract| contract.storage.restore(snapshot))
+    }
 }
 
 /// The kind of the account.
@@ -303,6 +335,10 @@ pub struct ContractStorage {
     count_reads: Cell<usize>,
     /// The total number of writes to the storage.
     count_writes: usize,
+    /// The number of reads to the storage per key.
+    count_reads_at: RefCell<BTreeMap<Key, usize>>,
+    /// The number of writes to the storage per key.
+    count_writes_at: BTreeMap<Key, usize>,
 }
 
 impl ContractStorage {
@@ -312,6 +348,8 @@ impl ContractStorage {
             entries: BTreeMap::new(),
             count_reads: Cell::new(0),
             count_writes: 0,
+            count_reads_at: RefCell::new(BTreeMap::new()),
+            count_writes_at: BTreeMap::new(),
         }
     }
 
@@ -320,12 +358,27 @@ impl ContractStorage {
         (self.count_reads.get(), self.count_writes)
     }
 
+    /// Returns the number of reads and writes from and to the contract storage
+    /// at the given key.
+    pub fn get_rw_at(&self, at: &Key) -> (usize, usize) {
+        let reads = self.count_reads_at.borrow().get(at).copied().unwrap_or(0);
+        let writes = self.count_writes_at.get(at).copied().unwrap_or(0);
+        (reads, writes)
+    }
+
+    /// Counts a write to the contract storage at the given key.
+    fn count_write_at(&mut self, at: Key) {
+        self.count_writes += 1;
+        *self.count_writes_at.entry(at).or_insert(0) += 1;
+    }
+
     /// Returns the decoded storage at the key if any.
     pub fn get_storage<T>(&self, at: Key) -> Result<Option<T>>
     where
         T: scale::Decode,
     {
         self.count_reads.set(self.count_reads.get() + 1);
+        *self.count_reads_at.borrow_mut().entry(at).or_insert(0) += 1;
         self.entries
             .get(&at)
             .map(|encoded| T::decode(&mut &encoded[..]))
@@ -338,13 +391,38 @@ impl ContractStorage {
     where
         T: scale::Encode,
     {
-        self.count_writes += 1;
+        self.count_write_at(at);
         self.entries.insert(at, new_value.encode());
     }
 
     /// Removes the value from storage entries at the given key.
     pub fn clear_storage(&mut self, at: Key) {
-        self.count_writes += 1;
+        self.count_write_at(at);
         self.entries.remove(&at);
     }
+
+    /// Returns a snapshot of the current storage entries.
+    pub fn snapshot(&self) -> StorageSnapshot {
+        StorageSnapshot {
+            entries: self.entries.clone(),
+        }
+    }
+
+    /// Restores the storage entries from the given snapshot.
+    ///
+    /// # Note
+    ///
+    /// This does not count as a write to the storage.
+    pub fn restore(&mut self, snapshot: StorageSnapshot) {
+        self.entries = snapshot.entries;
+    }
+}
+
+/// A snapshot of the entries of a contract storage.
+///
+/// Used to roll back changes to the contract storage upon a reverted execution.
+#[derive(Debug, Clone)]
+pub struct StorageSnapshot {
+    /// The snapshotted entries of the contract storage.
+    entries: BTreeMap<Key, Vec<u8>>,
 }
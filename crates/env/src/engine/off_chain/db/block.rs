@@ -63,6 +63,14 @@ impl Block {
         self.number.decode().map_err(Into::into)
     }
 
+    /// Sets the number of this block to the given block number.
+    pub fn set_number<T>(&mut self, new_number: T::BlockNumber) -> Result<()>
+    where
+        T: Environment,
+    {
+        self.number.assign(&new_number).map_err(Into::into)
+    }
+
     /// Returns the timestamp of the block.
     pub fn timestamp<T>(&self) -> Result<T::Timestamp>
     where
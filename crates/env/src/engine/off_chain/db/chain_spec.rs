@@ -106,4 +106,14 @@ impl ChainSpec {
     {
         self.block_time.decode().map_err(Into::into)
     }
+
+    /// Set the targeted block time for the chain.
+    ///
+    /// Every advanced block increases the block timestamp by this amount.
+    pub fn set_block_time<T>(&mut self, block_time: T::Timestamp)
+    where
+        T: Environment,
+    {
+        self.block_time = OffTimestamp::new(&block_time)
+    }
 }
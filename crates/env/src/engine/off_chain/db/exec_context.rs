@@ -20,6 +20,7 @@ use super::{
     },
     OffAccountId,
     OffBalance,
+    StorageSnapshot,
 };
 use crate::Environment;
 use ink_prelude::vec::Vec;
@@ -46,6 +47,13 @@ pub struct ExecContext {
     pub call_data: CallData,
     /// The output of the contract execution.
     pub output: Option<Bytes>,
+    /// The storage of the callee before the contract execution.
+    ///
+    /// # Note
+    ///
+    /// This is used to roll back the storage upon a reverted execution.
+    /// It is `None` if the callee is not a contract account.
+    pub storage_snapshot: Option<StorageSnapshot>,
 }
 
 impl ExecContext {
@@ -226,6 +234,7 @@ where
             gas: TypedEncoded::new(&gas),
             call_data: self.call_data.unwrap(),
             output: None,
+            storage_snapshot: None,
         }
     }
 }
@@ -0,0 +1,43 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! ECDSA public key recovery on the secp256k1 curve.
+//!
+//! Used by the off-chain environment in place of the `seal_ecdsa_recover` host
+//! function.
+
+use secp256k1::{
+    Message,
+    RecoveryId,
+    Signature,
+};
+
+/// Recovers the compressed public key from the signature and the signed message hash.
+///
+/// The signature consists of the 32 bytes `r` and `s` followed by the recovery id
+/// which is either within `0..=3` or within `27..=30` for Ethereum-style signatures.
+///
+/// Returns `None` if no public key can be recovered from the signature.
+pub fn ecdsa_recover(signature: &[u8; 65], message_hash: &[u8; 32]) -> Option<[u8; 33]> {
+    let recovery_id = match signature[64] {
+        id @ 27..=30 => id - 27,
+        id => id,
+    };
+    let recovery_id = RecoveryId::parse(recovery_id).ok()?;
+    let signature = Signature::parse(arrayref::array_ref!(signature, 0, 64));
+    let message = Message::parse(message_hash);
+    secp256k1::recover(&message, &signature, &recovery_id)
+        .ok()
+        .map(|public_key| public_key.serialize_compressed())
+}
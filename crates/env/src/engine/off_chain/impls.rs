@@ -12,14 +12,18 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+#[cfg(feature = "ink-unstable-ecdsa")]
+use super::ecdsa;
 use super::{
+    super::twox,
+    contract_calls::ContractCallFn,
     hashing,
     Account,
+    CallData,
     EnvInstance,
 };
 use crate::{
     call::{
-        utils::ReturnType,
         CallParams,
         CreateParams,
     },
@@ -30,6 +34,8 @@ use crate::{
         HashOutput,
         Keccak256,
         Sha2x256,
+        Twox128,
+        Twox256,
     },
     topics::Topics,
     EnvBackend,
@@ -117,6 +123,30 @@ impl CryptoHash for Keccak256 {
     }
 }
 
+impl CryptoHash for Twox128 {
+    fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type) {
+        type OutputType = [u8; 16];
+        static_assertions::assert_type_eq_all!(
+            <Twox128 as HashOutput>::Type,
+            OutputType
+        );
+        let output: &mut OutputType = arrayref::array_mut_ref!(output, 0, 16);
+        twox::twox_128(input, output);
+    }
+}
+
+impl CryptoHash for Twox256 {
+    fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type) {
+        type OutputType = [u8; 32];
+        static_assertions::assert_type_eq_all!(
+            <Twox256 as HashOutput>::Type,
+            OutputType
+        );
+        let output: &mut OutputType = arrayref::array_mut_ref!(output, 0, 32);
+        twox::twox_256(input, output);
+    }
+}
+
 impl EnvBackend for EnvInstance {
     fn set_contract_storage<V>(&mut self, key: &Key, value: &V)
     where
@@ -144,6 +174,18 @@ impl EnvBackend for EnvInstance {
         }
     }
 
+    #[cfg(feature = "ink-unstable-runtime-storage")]
+    fn get_runtime_storage<R>(&mut self, key: &[u8]) -> Result<Option<R>>
+    where
+        R: scale::Decode,
+    {
+        self.runtime_storage
+            .get(key)
+            .map(|encoded| scale::Decode::decode(&mut &encoded[..]))
+            .transpose()
+            .map_err(Into::into)
+    }
+
     fn decode_input<T>(&mut self) -> Result<T>
     where
         T: scale::Decode,
@@ -167,7 +209,17 @@ impl EnvBackend for EnvInstance {
             .exec_context_mut()
             .expect("uninitialized execution context");
         ctx.output = Some(return_value.encode());
-        std::process::exit(flags.into_u32() as i32)
+        if flags.is_reverted() {
+            if let Some(snapshot) = ctx.storage_snapshot.clone() {
+                self.callee_account_mut()
+                    .restore_storage(snapshot)
+                    .expect("callee with storage snapshot must be a contract account");
+            }
+            self.revert_data = Some(return_value.encode());
+            panic!("the contract execution has been reverted")
+        }
+        self.return_value = Some(return_value.encode());
+        panic!("the contract execution has returned")
     }
 
     fn println(&mut self, content: &str) {
@@ -190,6 +242,19 @@ impl EnvBackend for EnvInstance {
         self.hash_bytes::<H>(&encoded[..], output)
     }
 
+    #[cfg(feature = "ink-unstable-ecdsa")]
+    fn ecdsa_recover(
+        &mut self,
+        signature: &[u8; 65],
+        message_hash: &[u8; 32],
+        output: &mut [u8; 33],
+    ) -> Result<()> {
+        let public_key = ecdsa::ecdsa_recover(signature, message_hash)
+            .ok_or(Error::EcdsaRecoverFailed)?;
+        output.copy_from_slice(&public_key);
+        Ok(())
+    }
+
     #[cfg(feature = "ink-unstable-chain-extensions")]
     fn call_chain_extension<I, O>(&mut self, func_id: u32, input: &I) -> Result<O>
     where
@@ -232,6 +297,52 @@ impl EnvInstance {
             .set_balance::<T>(dst_value + value)?;
         Ok(())
     }
+
+    /// Returns the account ID of the next contract instantiated from the code hash.
+    ///
+    /// The account ID is derived from the instantiating contract, the code hash
+    /// and the number of contracts instantiated so far. This makes the account IDs
+    /// deterministic and unique throughout a test.
+    fn next_contract_account_id<T>(&mut self, code_hash: &T::Hash) -> Result<T::AccountId>
+    where
+        T: Environment,
+    {
+        let instantiator = self.account_id::<T>()?;
+        let nonce = self.instantiation_nonce;
+        self.instantiation_nonce += 1;
+        let mut output = [0x00; 32];
+        hashing::blake2b_256(
+            &scale::Encode::encode(&(instantiator, code_hash, nonce)),
+            &mut output,
+        );
+        scale::Decode::decode(&mut &output[..]).map_err(Into::into)
+    }
+
+    /// Prepares the call to the mocked contract at the callee of the given
+    /// call parameters.
+    ///
+    /// Transfers the value of the call to the callee and returns the call handler
+    /// of the mocked contract together with the call data to invoke it with.
+    ///
+    /// # Note
+    ///
+    /// The handler must be invoked only after the borrow of the off-chain
+    /// environment has been released so that it can use the environment itself.
+    pub(super) fn prepare_contract_call<T, Args, R>(
+        &mut self,
+        call_params: &CallParams<T, Args, R>,
+    ) -> Result<(ContractCallFn, CallData)>
+    where
+        T: Environment,
+        Args: scale::Encode,
+    {
+        let callee = call_params.callee();
+        let handler = self.contract_call_handler.handler::<T>(callee)?;
+        let encoded_input = scale::Encode::encode(call_params.exec_input());
+        let call_data = <CallData as scale::Decode>::decode(&mut &encoded_input[..])?;
+        self.transfer_impl::<T>(callee.clone(), *call_params.transferred_value())?;
+        Ok((handler, call_data))
+    }
 }
 
 impl TypedEnvBackend for EnvInstance {
@@ -341,45 +452,39 @@ impl TypedEnvBackend for EnvInstance {
             .expect("could not encode rent allowance")
     }
 
-    fn invoke_contract<T, Args>(
-        &mut self,
-        _call_params: &CallParams<T, Args, ()>,
-    ) -> Result<()>
-    where
-        T: Environment,
-        Args: scale::Encode,
-    {
-        unimplemented!("off-chain environment does not support contract invokation")
-    }
-
-    fn eval_contract<T, Args, R>(
-        &mut self,
-        _call_params: &CallParams<T, Args, ReturnType<R>>,
-    ) -> Result<R>
-    where
-        T: Environment,
-        Args: scale::Encode,
-        R: scale::Decode,
-    {
-        unimplemented!("off-chain environment does not support contract evaluation")
-    }
-
     fn instantiate_contract<T, Args, C>(
         &mut self,
-        _params: &CreateParams<T, Args, C>,
+        params: &CreateParams<T, Args, C>,
     ) -> Result<T::AccountId>
     where
         T: Environment,
         Args: scale::Encode,
     {
-        unimplemented!("off-chain environment does not support contract instantiation")
+        let account_id = self.next_contract_account_id::<T>(params.code_hash())?;
+        let endowment = *params.endowment();
+        // The transfer creates a user account at the fresh account ID holding
+        // exactly the endowment which we then turn into a contract account.
+        self.transfer_impl::<T>(account_id.clone(), endowment)?;
+        self.accounts.add_contract_account::<T>(
+            account_id.clone(),
+            endowment,
+            T::Balance::from(0u32),
+        );
+        Ok(account_id)
     }
 
-    fn terminate_contract<T>(&mut self, _beneficiary: T::AccountId) -> !
+    fn terminate_contract<T>(&mut self, beneficiary: T::AccountId) -> !
     where
         T: Environment,
     {
-        unimplemented!("off-chain environment does not support contract termination")
+        let remaining = self.balance::<T>().expect("could not read contract balance");
+        self.transfer_impl::<T>(beneficiary.clone(), remaining)
+            .expect("transferring the remaining balance must succeed");
+        // The terminated contract ceases to exist together with its storage.
+        let callee = self.account_id::<T>().expect("could not read callee");
+        self.accounts.remove_account::<T>(&callee);
+        self.terminated_beneficiary = Some(scale::Encode::encode(&beneficiary));
+        panic!("the contract has been terminated")
     }
 
     fn restore_contract<T>(
@@ -15,7 +15,10 @@
 mod call_data;
 #[cfg(feature = "ink-unstable-chain-extensions")]
 mod chain_extension;
+mod contract_calls;
 mod db;
+#[cfg(feature = "ink-unstable-ecdsa")]
+mod ecdsa;
 mod hashing;
 mod impls;
 pub mod test_api;
@@ -37,6 +40,7 @@ pub use self::{
     typed_encoded::TypedEncodedError,
 };
 use self::{
+    contract_calls::ContractCallHandler,
     db::{
         Account,
         AccountsDb,
@@ -56,9 +60,17 @@ use self::{
     },
 };
 use super::OnInstance;
-use crate::Environment;
+use crate::{
+    call::{
+        utils::ReturnType,
+        CallParams,
+    },
+    Environment,
+};
 use core::cell::RefCell;
 use derive_more::From;
+#[cfg(feature = "ink-unstable-runtime-storage")]
+use ink_prelude::collections::BTreeMap;
 
 #[derive(Debug, From, PartialEq, Eq)]
 pub enum OffChainError {
@@ -91,10 +103,23 @@ pub struct EnvInstance {
     /// Handler for registered chain extensions.
     #[cfg(feature = "ink-unstable-chain-extensions")]
     chain_extension_handler: ChainExtensionHandler,
+    /// Handler for calls to mocked contracts.
+    contract_call_handler: ContractCallHandler,
     /// Emitted events recorder.
     emitted_events: EmittedEventsRecorder,
     /// Set to true to disable clearing storage
     clear_storage_disabled: bool,
+    /// The encoded revert data of the last reverted contract execution if any.
+    revert_data: Option<Vec<u8>>,
+    /// The encoded return value of the last returned contract execution if any.
+    return_value: Option<Vec<u8>>,
+    /// The number of contracts instantiated so far.
+    instantiation_nonce: u64,
+    /// The encoded beneficiary of the last terminated contract if any.
+    terminated_beneficiary: Option<Vec<u8>>,
+    /// The encoded values of the runtime storage under their raw keys.
+    #[cfg(feature = "ink-unstable-runtime-storage")]
+    runtime_storage: BTreeMap<Vec<u8>, Vec<u8>>,
 }
 
 impl EnvInstance {
@@ -108,8 +133,15 @@ impl EnvInstance {
             console: Console::new(),
             #[cfg(feature = "ink-unstable-chain-extensions")]
             chain_extension_handler: ChainExtensionHandler::new(),
+            contract_call_handler: ContractCallHandler::new(),
             emitted_events: EmittedEventsRecorder::new(),
             clear_storage_disabled: false,
+            revert_data: None,
+            return_value: None,
+            instantiation_nonce: 0,
+            terminated_beneficiary: None,
+            #[cfg(feature = "ink-unstable-runtime-storage")]
+            runtime_storage: BTreeMap::new(),
         }
     }
 
@@ -140,8 +172,15 @@ impl EnvInstance {
         self.console.reset();
         #[cfg(feature = "ink-unstable-chain-extensions")]
         self.chain_extension_handler.reset();
+        self.contract_call_handler.reset();
         self.emitted_events.reset();
         self.clear_storage_disabled = false;
+        self.revert_data = None;
+        self.return_value = None;
+        self.instantiation_nonce = 0;
+        self.terminated_beneficiary = None;
+        #[cfg(feature = "ink-unstable-runtime-storage")]
+        self.runtime_storage.clear();
     }
 
     /// Initializes the whole off-chain environment.
@@ -206,7 +245,7 @@ impl EnvInstance {
         // The below selector bytes are incorrect but since calling doesn't work
         // yet we do not have to fix this now.
         let selector_bytes_for_call = [0x00; 4];
-        self.exec_context.push(
+        self.push_exec_context(
             ExecContext::build::<T>()
                 .caller(default_accounts.alice)
                 .callee(contract_account_id)
@@ -218,12 +257,30 @@ impl EnvInstance {
         Ok(())
     }
 
+    /// Pushes the execution context onto the stack of execution contexts.
+    ///
+    /// # Note
+    ///
+    /// Takes a snapshot of the storage of the callee so that its changes
+    /// to the contract storage can be rolled back if the execution reverts.
+    fn push_exec_context(&mut self, mut exec_context: ExecContext) {
+        exec_context.storage_snapshot = self
+            .accounts
+            .get_account_off(&exec_context.callee)
+            .and_then(|account| account.storage_snapshot().ok());
+        self.exec_context.push(exec_context);
+    }
+
     /// Advances the chain by a single block.
+    ///
+    /// The new block follows up on the number of the current block and its
+    /// timestamp is advanced by the block time of the chain specification.
     pub fn advance_block<T>(&mut self) -> crate::Result<()>
     where
         T: Environment,
     {
-        let new_block_number = T::BlockNumber::from(self.blocks.len() as u32);
+        let new_block_number =
+            self.current_block()?.number::<T>()? + T::BlockNumber::from(1u32);
         let new_timestamp = self.current_block()?.timestamp::<T>()?
             + self.chain_spec.block_time::<T>()?;
         self.blocks
@@ -275,3 +332,52 @@ impl OnInstance for EnvInstance {
         INSTANCE.with(|instance| f(&mut instance.borrow_mut()))
     }
 }
+
+/// Calls the mocked contract at the callee of the given call parameters.
+///
+/// # Note
+///
+/// Other than the [`TypedEnvBackend`](`crate::backend::TypedEnvBackend`)
+/// implementation this releases the borrow of the off-chain environment before
+/// invoking the mocked contract so that its handler can use the environment.
+///
+/// # Panics
+///
+/// If the mocked contract calls itself recursively.
+fn call_mocked_contract<T, Args, R>(
+    call_params: &CallParams<T, Args, R>,
+) -> crate::Result<Vec<u8>>
+where
+    T: Environment,
+    Args: scale::Encode,
+{
+    let (handler, call_data) = <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.prepare_contract_call(call_params)
+    })?;
+    let output = (*handler.borrow_mut())(call_data);
+    Ok(output)
+}
+
+/// Invokes the mocked contract at the callee of the given call parameters.
+pub fn invoke_contract<T, Args>(
+    call_params: &CallParams<T, Args, ()>,
+) -> crate::Result<()>
+where
+    T: Environment,
+    Args: scale::Encode,
+{
+    call_mocked_contract(call_params).map(|_output| ())
+}
+
+/// Evaluates the mocked contract at the callee of the given call parameters.
+pub fn eval_contract<T, Args, R>(
+    call_params: &CallParams<T, Args, ReturnType<R>>,
+) -> crate::Result<R>
+where
+    T: Environment,
+    Args: scale::Encode,
+    R: scale::Decode,
+{
+    let output = call_mocked_contract(call_params)?;
+    scale::Decode::decode(&mut &output[..]).map_err(Into::into)
+}
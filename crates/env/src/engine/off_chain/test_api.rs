@@ -25,6 +25,7 @@ use super::{
     db::ExecContext,
     AccountError,
     EnvInstance,
+    OffChainError,
     OnInstance,
 };
 use crate::{
@@ -32,6 +33,7 @@ use crate::{
     Result,
 };
 use ink_prelude::string::String;
+use ink_primitives::Key;
 
 /// Pushes a contract execution context.
 ///
@@ -51,7 +53,7 @@ pub fn push_execution_context<T>(
     T: Environment,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        instance.exec_context.push(
+        instance.push_exec_context(
             ExecContext::build::<T>()
                 .caller(caller)
                 .callee(callee)
@@ -194,6 +196,48 @@ where
     })
 }
 
+/// Registers a mocked contract at the given account.
+///
+/// # Note
+///
+/// Calls to the account via [`crate::invoke_contract`] and [`crate::eval_contract`]
+/// are handled by the given closure. It receives the call data of the call and
+/// returns the encoded output that the caller decodes.
+///
+/// The closure is invoked outside of the off-chain environment so that it can use
+/// the `ink_env` API itself, e.g. to query [`crate::caller`] or to access storage.
+/// It must not call its own mocked contract recursively, though.
+///
+/// Replaces the mocked contract previously registered at the account if any.
+pub fn register_contract<T, F>(callee: T::AccountId, handler: F)
+where
+    T: Environment,
+    F: FnMut(CallData) -> Vec<u8> + 'static,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .contract_call_handler
+            .register::<T, F>(&callee, handler);
+    })
+}
+
+/// Sets the value stored under the given raw key in the runtime storage.
+///
+/// # Note
+///
+/// This allows to control what [`crate::get_runtime_storage`] returns.
+#[cfg(feature = "ink-unstable-runtime-storage")]
+pub fn set_runtime_storage<V>(key: &[u8], value: &V)
+where
+    V: scale::Encode,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .runtime_storage
+            .insert(key.to_vec(), scale::Encode::encode(value));
+    })
+}
+
 /// Set the entropy hash of the current block.
 ///
 /// # Note
@@ -209,6 +253,98 @@ where
     .map_err(Into::into)
 }
 
+/// Sets the number of the current block.
+///
+/// # Note
+///
+/// This allows to control what [`crate::block_number`] returns.
+/// Blocks advanced afterwards via [`advance_block`] continue counting from
+/// the given block number.
+///
+/// # Errors
+///
+/// - If the underlying `block_number` type does not match.
+pub fn set_block_number<T>(block_number: T::BlockNumber) -> Result<()>
+where
+    T: Environment,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.current_block_mut()?.set_number::<T>(block_number)
+    })
+    .map_err(Into::into)
+}
+
+/// Sets the caller of the current contract execution.
+///
+/// # Note
+///
+/// This allows to control what [`crate::caller`] returns without pushing
+/// a new execution context. Use [`push_execution_context`] and
+/// [`pop_execution_context`] instead to emulate nested calls.
+///
+/// # Errors
+///
+/// - If there is no execution context.
+/// - If the underlying `caller` type does not match.
+pub fn set_caller<T>(caller: T::AccountId) -> Result<()>
+where
+    T: Environment,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .exec_context_mut()?
+            .caller
+            .assign(&caller)
+            .map_err(OffChainError::from)
+    })
+    .map_err(Into::into)
+}
+
+/// Sets the call data of the current contract execution.
+///
+/// # Note
+///
+/// This allows to control what [`crate::decode_input`] decodes so that
+/// the dispatch of a contract can be driven with encoded selectors and
+/// arguments.
+///
+/// # Errors
+///
+/// If there is no execution context.
+pub fn set_call_data(call_data: CallData) -> Result<()> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .exec_context_mut()
+            .map(|exec_context| exec_context.call_data = call_data)
+    })
+    .map_err(Into::into)
+}
+
+/// Sets the value transferred with the current contract execution.
+///
+/// # Note
+///
+/// This allows to control what [`crate::transferred_balance`] returns
+/// without pushing a new execution context.
+///
+/// # Errors
+///
+/// - If there is no execution context.
+/// - If the underlying `value` type does not match.
+pub fn set_value_transferred<T>(value: T::Balance) -> Result<()>
+where
+    T: Environment,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .exec_context_mut()?
+            .transferred_value
+            .assign(&value)
+            .map_err(OffChainError::from)
+    })
+    .map_err(Into::into)
+}
+
 /// Update the [ChainSpec](`crate::test::ChainSpec`) for the test environment
 pub fn update_chain_spec<F>(f: F) -> Result<()>
 where
@@ -252,7 +388,65 @@ pub fn recorded_events() -> impl Iterator<Item = EmittedEvent> {
     })
 }
 
+/// Clears the recorded emitted events.
+///
+/// # Note
+///
+/// This allows to assert on the events emitted by a single contract message
+/// without also seeing the events emitted by the preceding ones.
+pub fn reset_events() {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.emitted_events.reset())
+}
+
+/// Returns the encoded revert data of the last reverted contract execution if any.
+///
+/// # Note
+///
+/// A contract execution is reverted by [`crate::revert`] or by returning
+/// with the reverted flag set in [`crate::return_value`].
+pub fn recorded_revert_data() -> Option<Vec<u8>> {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.revert_data.clone())
+}
+
+/// Returns the encoded return value of the last returned contract execution if any.
+///
+/// # Note
+///
+/// A contract execution returns via [`crate::return_value`] without the reverted
+/// flag set. Since the off-chain environment cannot stop the execution it panics
+/// after recording the return value instead. Tests can catch this panic using
+/// [`std::panic::catch_unwind`] and then decode the recorded return value.
+pub fn recorded_return_value() -> Option<Vec<u8>> {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.return_value.clone())
+}
+
+/// Returns the beneficiary of the last terminated contract if any.
+///
+/// # Note
+///
+/// A contract is terminated via [`crate::terminate_contract`]. Since the off-chain
+/// environment cannot stop the execution it panics after transferring the remaining
+/// balance of the contract to the beneficiary instead. Tests can catch this panic
+/// using [`std::panic::catch_unwind`] and then query the beneficiary.
+pub fn recorded_termination_beneficiary<T>() -> Option<T::AccountId>
+where
+    T: Environment,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.terminated_beneficiary.as_ref().map(|encoded| {
+            scale::Decode::decode(&mut &encoded[..])
+                .expect("encountered invalid termination beneficiary")
+        })
+    })
+}
+
 /// Advances the chain by a single block.
+///
+/// # Note
+///
+/// Increments the block number by one and advances the block timestamp by the
+/// block time of the chain specification. Use [`update_chain_spec`] together
+/// with [`ChainSpec::set_block_time`] in order to configure the block time.
 pub fn advance_block<T>() -> Result<()>
 where
     T: Environment,
@@ -313,7 +507,13 @@ where
 /// # Note
 ///
 /// - Initializes the off-chain environment with default values that fit most
-/// uses cases.
+///   uses cases.
+/// - Resets all state of a previously initialized off-chain environment. This
+///   includes the accounts with their balances and contract storage, the storage
+///   read and write counters, the execution contexts, the blocks, the recorded
+///   events and printlns as well as the registered mocked contracts.
+/// - The off-chain environment is thread-local. Tests running in parallel on
+///   different threads therefore never share any state.
 pub fn initialize_or_reset_as_default<T>() -> Result<()>
 where
     T: Environment,
@@ -352,6 +552,29 @@ where
     })
 }
 
+/// Returns the number of reads and writes of the contract's storage at the given key.
+///
+/// # Note
+///
+/// The total number of reads and writes of all keys is returned by
+/// [`get_contract_storage_rw`].
+pub fn get_contract_storage_rw_at<T>(
+    account_id: &T::AccountId,
+    key: &Key,
+) -> Result<(usize, usize)>
+where
+    T: Environment,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .accounts
+            .get_account::<T>(account_id)
+            .ok_or_else(|| AccountError::no_account_for_id::<T>(account_id))
+            .map_err(Into::into)
+            .and_then(|account| account.get_storage_rw_at(key).map_err(Into::into))
+    })
+}
+
 /// Returns the account id of the currently executing contract.
 pub fn get_current_contract_account_id<T>() -> Result<T::AccountId>
 where
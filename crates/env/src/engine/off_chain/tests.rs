@@ -90,3 +90,804 @@ fn gas_price() -> crate::Result<()> {
         Ok(())
     })
 }
+
+#[test]
+fn revert_rolls_back_storage() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        let key = Key::from([0x42; 32]);
+        assert_eq!(crate::test::recorded_revert_data(), None);
+        crate::set_contract_storage(&key, &1);
+        // Pushing a new execution context marks the start of a new execution.
+        crate::test::push_execution_context::<crate::DefaultEnvironment>(
+            [0x01; 32].into(),
+            [0x07; 32].into(),
+            1_000_000,
+            0,
+            crate::test::CallData::new(crate::call::Selector::new([0x00; 4])),
+        );
+        crate::set_contract_storage(&key, &2);
+        crate::clear_contract_storage(&Key::from([0x77; 32]));
+        crate::set_contract_storage(&Key::from([0x77; 32]), &3);
+        let result = std::panic::catch_unwind(|| crate::revert(&"insufficient funds"));
+        assert!(result.is_err());
+        // All changes of the reverted execution have been rolled back.
+        assert_eq!(crate::get_contract_storage::<i32>(&key), Ok(Some(1)));
+        assert_eq!(
+            crate::get_contract_storage::<i32>(&Key::from([0x77; 32])),
+            Ok(None)
+        );
+        assert_eq!(
+            crate::test::recorded_revert_data(),
+            Some(scale::Encode::encode(&"insufficient funds"))
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn emitted_events_are_recorded() -> Result<()> {
+    use crate::topics::{
+        state,
+        Topics,
+        TopicsBuilder,
+        TopicsBuilderBackend,
+    };
+
+    #[derive(scale::Encode)]
+    struct Transfer {
+        from: [u8; 32],
+        value: u128,
+    }
+
+    impl Topics for Transfer {
+        type RemainingTopics = [state::HasRemainingTopics; 1];
+
+        fn topics<E, B>(
+            &self,
+            builder: TopicsBuilder<state::Uninit, E, B>,
+        ) -> <B as TopicsBuilderBackend<E>>::Output
+        where
+            E: crate::Environment,
+            B: TopicsBuilderBackend<E>,
+        {
+            builder.build::<Self>().push_topic(&self.from).finish()
+        }
+    }
+
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        assert_eq!(crate::test::recorded_events().count(), 0);
+        let transfer = Transfer {
+            from: [0x01; 32],
+            value: 100,
+        };
+        let encoded = scale::Encode::encode(&transfer);
+        crate::emit_event::<crate::DefaultEnvironment, _>(transfer);
+        crate::emit_event::<crate::DefaultEnvironment, _>(Transfer {
+            from: [0x02; 32],
+            value: 200,
+        });
+        // Events are recorded in their emission order.
+        let events = crate::test::recorded_events().collect::<Vec<_>>();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].topics.len(), 1);
+        assert_eq!(events[0].data, encoded);
+        assert_eq!(
+            events[1].data,
+            scale::Encode::encode(&Transfer {
+                from: [0x02; 32],
+                value: 200,
+            })
+        );
+        // Resetting the events only affects the already recorded ones.
+        crate::test::reset_events();
+        assert_eq!(crate::test::recorded_events().count(), 0);
+        crate::emit_event::<crate::DefaultEnvironment, _>(Transfer {
+            from: [0x03; 32],
+            value: 300,
+        });
+        assert_eq!(crate::test::recorded_events().count(), 1);
+        Ok(())
+    })
+}
+
+#[test]
+fn block_number_and_timestamp_work() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        assert_eq!(crate::block_number::<crate::DefaultEnvironment>(), Ok(0));
+        assert_eq!(crate::block_timestamp::<crate::DefaultEnvironment>(), Ok(0));
+        crate::test::advance_block::<crate::DefaultEnvironment>()?;
+        crate::test::advance_block::<crate::DefaultEnvironment>()?;
+        // Every block advances the timestamp by the default block time.
+        assert_eq!(crate::block_number::<crate::DefaultEnvironment>(), Ok(2));
+        assert_eq!(crate::block_timestamp::<crate::DefaultEnvironment>(), Ok(10));
+        Ok(())
+    })
+}
+
+#[test]
+fn set_block_number_and_block_time_work() -> Result<()> {
+    use crate::DefaultEnvironment;
+    crate::test::run_test::<DefaultEnvironment, _>(|_| {
+        crate::test::set_block_number::<DefaultEnvironment>(100)?;
+        assert_eq!(crate::block_number::<DefaultEnvironment>(), Ok(100));
+        assert_eq!(crate::block_timestamp::<DefaultEnvironment>(), Ok(0));
+        crate::test::update_chain_spec(|chain_spec| {
+            chain_spec.set_block_time::<DefaultEnvironment>(6_000)
+        })?;
+        // Advanced blocks continue counting from the set block number and
+        // move the timestamp forward by the configured block time.
+        for n in 1..=3 {
+            crate::test::advance_block::<DefaultEnvironment>()?;
+            assert_eq!(crate::block_number::<DefaultEnvironment>(), Ok(100 + n));
+            assert_eq!(
+                crate::block_timestamp::<DefaultEnvironment>(),
+                Ok(6_000 * u64::from(n))
+            );
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn set_value_transferred_works() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // The default execution context transfers some value.
+        assert_eq!(
+            crate::transferred_balance::<crate::DefaultEnvironment>(),
+            Ok(500)
+        );
+        crate::test::set_value_transferred::<crate::DefaultEnvironment>(1_000)?;
+        assert_eq!(
+            crate::transferred_balance::<crate::DefaultEnvironment>(),
+            Ok(1_000)
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn calls_to_registered_contracts_work() -> Result<()> {
+    use crate::call::{
+        build_call,
+        utils::ReturnType,
+        ExecutionInput,
+        Selector,
+    };
+    use crate::DefaultEnvironment;
+    type AccountId = <DefaultEnvironment as crate::Environment>::AccountId;
+
+    crate::test::run_test::<DefaultEnvironment, _>(|_| {
+        let contract =
+            crate::test::get_current_contract_account_id::<DefaultEnvironment>()?;
+        crate::test::set_account_balance::<DefaultEnvironment>(contract, 100)?;
+        let callee = AccountId::from([0x42; 32]);
+        let double = Selector::new([0xDE, 0xAD, 0xBE, 0xEF]);
+        let call_double = |value: i32| {
+            build_call::<DefaultEnvironment>()
+                .callee(callee)
+                .gas_limit(5000)
+                .transferred_value(10)
+                .exec_input(ExecutionInput::new(double).push_arg(value))
+                .returns::<ReturnType<i32>>()
+                .fire()
+        };
+        // Calling an account without a registered contract fails.
+        assert_eq!(call_double(21), Err(crate::Error::NotCallable));
+        crate::test::register_contract::<DefaultEnvironment, _>(
+            callee,
+            move |call_data| {
+                assert_eq!(call_data.selector(), double);
+                let value = <i32 as scale::Decode>::decode(&mut call_data.params())
+                    .expect("the argument must be an `i32`");
+                scale::Encode::encode(&(value * 2))
+            },
+        );
+        assert_eq!(call_double(21), Ok(42));
+        // The transferred value is sent to the callee.
+        assert_eq!(
+            crate::test::get_account_balance::<DefaultEnvironment>(contract),
+            Ok(90)
+        );
+        assert_eq!(
+            crate::test::get_account_balance::<DefaultEnvironment>(callee),
+            Ok(10)
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn registered_contracts_can_use_the_environment() -> Result<()> {
+    use crate::call::{
+        build_call,
+        utils::ReturnType,
+        ExecutionInput,
+        Selector,
+    };
+    use crate::DefaultEnvironment;
+    type AccountId = <DefaultEnvironment as crate::Environment>::AccountId;
+
+    crate::test::run_test::<DefaultEnvironment, _>(|accounts| {
+        let callee = AccountId::from([0x42; 32]);
+        let key = Key::from([0x01; 32]);
+        // The mocked contract queries the environment while handling the call.
+        crate::test::register_contract::<DefaultEnvironment, _>(callee, move |_| {
+            let caller = crate::caller::<DefaultEnvironment>()
+                .expect("the caller must be available");
+            crate::set_contract_storage(&key, &7u32);
+            scale::Encode::encode(&caller)
+        });
+        let caller = build_call::<DefaultEnvironment>()
+            .callee(callee)
+            .gas_limit(5000)
+            .transferred_value(0)
+            .exec_input(ExecutionInput::new(Selector::new([0x00; 4])))
+            .returns::<ReturnType<AccountId>>()
+            .fire();
+        assert_eq!(caller, Ok(accounts.alice));
+        assert_eq!(crate::get_contract_storage::<u32>(&key), Ok(Some(7)));
+        Ok(())
+    })
+}
+
+/// The contract instantiated by the tests below.
+struct Child(<crate::DefaultEnvironment as crate::Environment>::AccountId);
+
+impl crate::call::FromAccountId<crate::DefaultEnvironment> for Child {
+    fn from_account_id(
+        account_id: <crate::DefaultEnvironment as crate::Environment>::AccountId,
+    ) -> Self {
+        Self(account_id)
+    }
+}
+
+#[test]
+fn instantiate_contract_works() -> Result<()> {
+    use crate::call::{
+        build_create,
+        ExecutionInput,
+        Selector,
+    };
+    use crate::DefaultEnvironment;
+    type Hash = <DefaultEnvironment as crate::Environment>::Hash;
+
+    crate::test::run_test::<DefaultEnvironment, _>(|_| {
+        let contract =
+            crate::test::get_current_contract_account_id::<DefaultEnvironment>()?;
+        crate::test::set_account_balance::<DefaultEnvironment>(contract, 100)?;
+        let instantiate = |code_hash: Hash| {
+            build_create::<DefaultEnvironment, Child>()
+                .code_hash(code_hash)
+                .gas_limit(4000)
+                .endowment(25)
+                .exec_input(ExecutionInput::new(Selector::new([0x00; 4])))
+                .params()
+                .instantiate()
+        };
+        let Child(first) = instantiate(Hash::from([0x01; 32]))?;
+        let Child(second) = instantiate(Hash::from([0x01; 32]))?;
+        // Every instantiation yields a fresh contract account.
+        assert_ne!(first, second);
+        assert_ne!(first, contract);
+        for child in [first, second].iter().cloned() {
+            assert_eq!(
+                crate::test::get_account_balance::<DefaultEnvironment>(child),
+                Ok(25)
+            );
+            assert!(crate::test::get_contract_rent_allowance::<DefaultEnvironment>(
+                child
+            )
+            .is_ok());
+        }
+        assert_eq!(
+            crate::test::get_account_balance::<DefaultEnvironment>(contract),
+            Ok(50)
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn instantiated_account_ids_are_deterministic() -> Result<()> {
+    use crate::call::{
+        build_create,
+        ExecutionInput,
+        Selector,
+    };
+    use crate::DefaultEnvironment;
+    type Hash = <DefaultEnvironment as crate::Environment>::Hash;
+
+    // Runs the same test twice and collects the instantiated account IDs.
+    let mut children = Vec::new();
+    for _ in 0..2 {
+        crate::test::run_test::<DefaultEnvironment, _>(|_| {
+            let Child(child) = build_create::<DefaultEnvironment, Child>()
+                .code_hash(Hash::from([0x01; 32]))
+                .gas_limit(4000)
+                .endowment(0)
+                .exec_input(ExecutionInput::new(Selector::new([0x00; 4])))
+                .params()
+                .instantiate()?;
+            children.push(child);
+            Ok(())
+        })?;
+    }
+    assert_eq!(children[0], children[1]);
+    Ok(())
+}
+
+#[test]
+fn caller_is_typed_account_id() -> Result<()> {
+    use crate::DefaultEnvironment;
+    crate::test::run_test::<DefaultEnvironment, _>(|accounts| {
+        // The default execution context is called by alice.
+        assert_eq!(crate::caller::<DefaultEnvironment>(), Ok(accounts.alice));
+        let contract =
+            crate::test::get_current_contract_account_id::<DefaultEnvironment>()?;
+        crate::test::push_execution_context::<DefaultEnvironment>(
+            accounts.bob,
+            contract,
+            1_000_000,
+            0,
+            crate::test::CallData::new(crate::call::Selector::new([0x00; 4])),
+        );
+        assert_eq!(crate::caller::<DefaultEnvironment>(), Ok(accounts.bob));
+        crate::test::pop_execution_context();
+        assert_eq!(crate::caller::<DefaultEnvironment>(), Ok(accounts.alice));
+        Ok(())
+    })
+}
+
+#[test]
+fn account_id_is_the_callee() -> Result<()> {
+    use crate::DefaultEnvironment;
+    type AccountId = <DefaultEnvironment as crate::Environment>::AccountId;
+    crate::test::run_test::<DefaultEnvironment, _>(|accounts| {
+        let contract =
+            crate::test::get_current_contract_account_id::<DefaultEnvironment>()?;
+        assert_eq!(crate::account_id::<DefaultEnvironment>(), Ok(contract));
+        // The executed contract is the callee of the current execution context.
+        let other = AccountId::from([0x42; 32]);
+        crate::test::push_execution_context::<DefaultEnvironment>(
+            accounts.alice,
+            other,
+            1_000_000,
+            0,
+            crate::test::CallData::new(crate::call::Selector::new([0x00; 4])),
+        );
+        assert_eq!(crate::account_id::<DefaultEnvironment>(), Ok(other));
+        assert_eq!(
+            crate::test::get_current_contract_account_id::<DefaultEnvironment>(),
+            Ok(other)
+        );
+        crate::test::pop_execution_context();
+        assert_eq!(crate::account_id::<DefaultEnvironment>(), Ok(contract));
+        Ok(())
+    })
+}
+
+#[test]
+fn balance_reads_the_contract_balance() -> Result<()> {
+    use crate::DefaultEnvironment;
+    crate::test::run_test::<DefaultEnvironment, _>(|accounts| {
+        let contract =
+            crate::test::get_current_contract_account_id::<DefaultEnvironment>()?;
+        assert_eq!(crate::balance::<DefaultEnvironment>(), Ok(0));
+        crate::test::set_account_balance::<DefaultEnvironment>(contract, 1_000)?;
+        assert_eq!(crate::balance::<DefaultEnvironment>(), Ok(1_000));
+        // Arranging the balance of other accounts does not affect the contract.
+        crate::test::set_account_balance::<DefaultEnvironment>(accounts.charlie, 42)?;
+        assert_eq!(
+            crate::test::get_account_balance::<DefaultEnvironment>(accounts.charlie),
+            Ok(42)
+        );
+        assert_eq!(crate::balance::<DefaultEnvironment>(), Ok(1_000));
+        Ok(())
+    })
+}
+
+#[test]
+fn transfer_works() -> Result<()> {
+    use crate::DefaultEnvironment;
+    crate::test::run_test::<DefaultEnvironment, _>(|accounts| {
+        let contract =
+            crate::test::get_current_contract_account_id::<DefaultEnvironment>()?;
+        crate::test::set_account_balance::<DefaultEnvironment>(contract, 100)?;
+        crate::transfer::<DefaultEnvironment>(accounts.django, 60)?;
+        assert_eq!(crate::balance::<DefaultEnvironment>(), Ok(40));
+        assert_eq!(
+            crate::test::get_account_balance::<DefaultEnvironment>(accounts.django),
+            Ok(60)
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn transfer_with_insufficient_funds_fails() -> Result<()> {
+    use crate::DefaultEnvironment;
+    crate::test::run_test::<DefaultEnvironment, _>(|accounts| {
+        let contract =
+            crate::test::get_current_contract_account_id::<DefaultEnvironment>()?;
+        crate::test::set_account_balance::<DefaultEnvironment>(contract, 100)?;
+        assert_eq!(
+            crate::transfer::<DefaultEnvironment>(accounts.django, 101),
+            Err(crate::Error::TransferFailed)
+        );
+        // No balance has been moved.
+        assert_eq!(crate::balance::<DefaultEnvironment>(), Ok(100));
+        assert_eq!(
+            crate::test::get_account_balance::<DefaultEnvironment>(accounts.django),
+            Ok(0)
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn return_flags_works() {
+    use crate::ReturnFlags;
+    assert!(!ReturnFlags::default().is_reverted());
+    assert_eq!(ReturnFlags::default().into_u32(), 0);
+    assert!(ReturnFlags::default().set_reverted(true).is_reverted());
+    assert_eq!(ReturnFlags::default().set_reverted(true).into_u32(), 1);
+    // Unsetting the reverted flag clears it again.
+    let flags = ReturnFlags::default().set_reverted(true).set_reverted(false);
+    assert!(!flags.is_reverted());
+    assert_eq!(flags.into_u32(), 0);
+}
+
+#[test]
+fn random_is_derived_from_block_entropy() -> Result<()> {
+    use crate::DefaultEnvironment;
+    type Hash = <DefaultEnvironment as crate::Environment>::Hash;
+    crate::test::run_test::<DefaultEnvironment, _>(|_| {
+        let entropy = Hash::from([0x2A; 32]);
+        crate::test::set_block_entropy::<DefaultEnvironment>(entropy)?;
+        // Without a subject the random hash is the entropy itself.
+        assert_eq!(crate::random::<DefaultEnvironment>(&[]), Ok(entropy));
+        // The same subject yields the same random hash.
+        let random = crate::random::<DefaultEnvironment>(b"lottery")?;
+        assert_ne!(random, entropy);
+        assert_eq!(crate::random::<DefaultEnvironment>(b"lottery"), Ok(random));
+        assert_ne!(crate::random::<DefaultEnvironment>(b"raffle"), Ok(random));
+        Ok(())
+    })
+}
+
+#[test]
+fn set_caller_works() -> Result<()> {
+    use crate::DefaultEnvironment;
+    crate::test::run_test::<DefaultEnvironment, _>(|accounts| {
+        assert_eq!(crate::caller::<DefaultEnvironment>(), Ok(accounts.alice));
+        crate::test::set_caller::<DefaultEnvironment>(accounts.bob)?;
+        assert_eq!(crate::caller::<DefaultEnvironment>(), Ok(accounts.bob));
+        // The executed contract stays the same.
+        assert_eq!(
+            crate::account_id::<DefaultEnvironment>(),
+            crate::test::get_current_contract_account_id::<DefaultEnvironment>()
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn initialize_or_reset_as_default_resets_state() -> Result<()> {
+    use crate::DefaultEnvironment;
+    let key = Key::from([0x42; 32]);
+    crate::test::run_test::<DefaultEnvironment, _>(|accounts| {
+        let contract =
+            crate::test::get_current_contract_account_id::<DefaultEnvironment>()?;
+        crate::set_contract_storage(&key, &1);
+        crate::test::set_account_balance::<DefaultEnvironment>(accounts.eve, 42)?;
+        crate::test::set_caller::<DefaultEnvironment>(accounts.bob)?;
+        crate::test::advance_block::<DefaultEnvironment>()?;
+        crate::debug_println("hello");
+        assert_eq!(
+            crate::test::get_contract_storage_rw::<DefaultEnvironment>(&contract),
+            Ok((0, 1))
+        );
+        Ok(())
+    })?;
+    crate::test::run_test::<DefaultEnvironment, _>(|accounts| {
+        let contract =
+            crate::test::get_current_contract_account_id::<DefaultEnvironment>()?;
+        assert_eq!(crate::get_contract_storage::<i32>(&key), Ok(None));
+        assert_eq!(
+            crate::test::get_contract_storage_rw::<DefaultEnvironment>(&contract),
+            Ok((1, 0))
+        );
+        assert_eq!(
+            crate::test::get_account_balance::<DefaultEnvironment>(accounts.eve),
+            Ok(0)
+        );
+        assert_eq!(crate::caller::<DefaultEnvironment>(), Ok(accounts.alice));
+        assert_eq!(crate::block_number::<DefaultEnvironment>(), Ok(0));
+        assert_eq!(crate::test::recorded_printlns().count(), 0);
+        Ok(())
+    })
+}
+
+#[test]
+fn set_call_data_works() -> Result<()> {
+    use crate::call::Selector;
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        let selector = Selector::new([0xCA, 0xFE, 0xBA, 0xBE]);
+        let mut call_data = crate::test::CallData::new(selector);
+        call_data.push_arg(&42u32);
+        call_data.push_arg(&true);
+        crate::test::set_call_data(call_data)?;
+        assert_eq!(
+            crate::decode_input::<([u8; 4], u32, bool)>(),
+            Ok(([0xCA, 0xFE, 0xBA, 0xBE], 42, true))
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn return_value_is_recorded() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        let key = Key::from([0x42; 32]);
+        assert_eq!(crate::test::recorded_return_value(), None);
+        let result = std::panic::catch_unwind(|| {
+            crate::set_contract_storage(&key, &1);
+            crate::return_value(crate::ReturnFlags::default(), &Some(42u128))
+        });
+        assert!(result.is_err());
+        let encoded = crate::test::recorded_return_value()
+            .expect("the return value must have been recorded");
+        assert_eq!(
+            <Option<u128> as scale::Decode>::decode(&mut &encoded[..]),
+            Ok(Some(42))
+        );
+        // Returning does not roll back any changes and is no revert.
+        assert_eq!(crate::get_contract_storage::<i32>(&key), Ok(Some(1)));
+        assert_eq!(crate::test::recorded_revert_data(), None);
+        Ok(())
+    })
+}
+
+#[test]
+fn storage_rw_at_counts_per_key() -> Result<()> {
+    use crate::DefaultEnvironment;
+    crate::test::run_test::<DefaultEnvironment, _>(|_| {
+        let contract =
+            crate::test::get_current_contract_account_id::<DefaultEnvironment>()?;
+        let rw_at = |key: &Key| {
+            crate::test::get_contract_storage_rw_at::<DefaultEnvironment>(&contract, key)
+        };
+        let key_a = Key::from([0x01; 32]);
+        let key_b = Key::from([0x02; 32]);
+        assert_eq!(rw_at(&key_a), Ok((0, 0)));
+        crate::set_contract_storage(&key_a, &1);
+        crate::set_contract_storage(&key_a, &2);
+        let _ = crate::get_contract_storage::<i32>(&key_a)?;
+        crate::clear_contract_storage(&key_b);
+        let _ = crate::get_contract_storage::<i32>(&key_b)?;
+        let _ = crate::get_contract_storage::<i32>(&key_b)?;
+        assert_eq!(rw_at(&key_a), Ok((1, 2)));
+        assert_eq!(rw_at(&key_b), Ok((2, 1)));
+        // The aggregate counters sum up all keys.
+        assert_eq!(
+            crate::test::get_contract_storage_rw::<DefaultEnvironment>(&contract),
+            Ok((3, 3))
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn decode_input_fails_on_malformed_input() -> Result<()> {
+    use crate::call::Selector;
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        let mut call_data = crate::test::CallData::new(Selector::new([0x00; 4]));
+        call_data.push_arg(&0x42u8);
+        crate::test::set_call_data(call_data)?;
+        // The input is too short to hold a `u32` argument after the selector.
+        assert!(matches!(
+            crate::decode_input::<([u8; 4], u32)>(),
+            Err(crate::Error::Decode(_))
+        ));
+        // The input can still be decoded as its actual shape.
+        assert_eq!(crate::decode_input::<([u8; 4], u8)>(), Ok(([0x00; 4], 0x42)));
+        Ok(())
+    })
+}
+
+#[test]
+#[cfg(feature = "ink-unstable-runtime-storage")]
+fn runtime_storage_works() -> Result<()> {
+    use crate::hash::Twox128;
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        // The storage key of `Balances::TotalIssuance` as used by the runtime.
+        let mut key = [0x00_u8; 32];
+        crate::hash_bytes::<Twox128>(b"Balances", arrayref::array_mut_ref!(key, 0, 16));
+        crate::hash_bytes::<Twox128>(
+            b"TotalIssuance",
+            arrayref::array_mut_ref!(key, 16, 16),
+        );
+        assert_eq!(crate::get_runtime_storage::<u128>(&key), Ok(None));
+        crate::test::set_runtime_storage(&key, &1_000_u128);
+        assert_eq!(crate::get_runtime_storage::<u128>(&key), Ok(Some(1_000)));
+        // The runtime storage is separate from the contract storage.
+        assert_eq!(
+            crate::get_contract_storage::<u128>(&Key::from(key)),
+            Ok(None)
+        );
+        // Decoding the value as a mismatching type fails.
+        assert!(matches!(
+            crate::get_runtime_storage::<[u8; 32]>(&key),
+            Err(crate::Error::Decode(_))
+        ));
+        Ok(())
+    })
+}
+
+#[test]
+fn custom_environment_works() -> Result<()> {
+    use crate::{
+        DefaultEnvironment,
+        Environment,
+    };
+
+    /// An environment with types that differ from the default environment.
+    enum CustomEnvironment {}
+
+    impl Environment for CustomEnvironment {
+        const MAX_EVENT_TOPICS: usize = 2;
+
+        type AccountId = <DefaultEnvironment as Environment>::AccountId;
+        type Balance = u64;
+        type Hash = <DefaultEnvironment as Environment>::Hash;
+        type Timestamp = u32;
+        type BlockNumber = u32;
+    }
+
+    crate::test::run_test::<CustomEnvironment, _>(|accounts| {
+        // All environmental values are of the types of the custom environment.
+        let caller: <CustomEnvironment as Environment>::AccountId =
+            crate::caller::<CustomEnvironment>()?;
+        assert_eq!(caller, accounts.alice);
+        let balance: u64 = crate::balance::<CustomEnvironment>()?;
+        assert_eq!(balance, 0);
+        let block_number: u32 = crate::block_number::<CustomEnvironment>()?;
+        assert_eq!(block_number, 0);
+        crate::test::advance_block::<CustomEnvironment>()?;
+        assert_eq!(crate::block_number::<CustomEnvironment>(), Ok(1));
+        // Querying with mismatching types is detected.
+        assert!(crate::block_number::<DefaultEnvironment>().is_err());
+        Ok(())
+    })
+}
+
+#[test]
+fn debug_println_is_recorded() -> Result<()> {
+    crate::test::run_test::<crate::DefaultEnvironment, _>(|_| {
+        assert_eq!(crate::test::recorded_printlns().count(), 0);
+        crate::debug_println("hello");
+        crate::debug_println(&format!("value = {}", 42));
+        assert_eq!(
+            crate::test::recorded_printlns().collect::<Vec<_>>(),
+            vec!["hello", "value = 42"]
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn terminate_contract_transfers_remaining_balance() -> Result<()> {
+    use crate::DefaultEnvironment;
+    crate::test::run_test::<DefaultEnvironment, _>(|accounts| {
+        let contract =
+            crate::test::get_current_contract_account_id::<DefaultEnvironment>()?;
+        crate::test::set_account_balance::<DefaultEnvironment>(contract, 100)?;
+        crate::test::set_account_balance::<DefaultEnvironment>(accounts.eve, 0)?;
+        crate::set_contract_storage(&Key::from([0x42; 32]), &1u8);
+        assert_eq!(
+            crate::test::recorded_termination_beneficiary::<DefaultEnvironment>(),
+            None
+        );
+        let result = std::panic::catch_unwind(|| {
+            crate::terminate_contract::<DefaultEnvironment>(accounts.eve)
+        });
+        assert!(result.is_err());
+        assert_eq!(
+            crate::test::recorded_termination_beneficiary::<DefaultEnvironment>(),
+            Some(accounts.eve)
+        );
+        assert_eq!(
+            crate::test::get_account_balance::<DefaultEnvironment>(accounts.eve),
+            Ok(100)
+        );
+        // The terminated contract has been removed together with its storage.
+        assert!(
+            crate::test::get_account_balance::<DefaultEnvironment>(contract).is_err()
+        );
+        assert!(
+            crate::test::get_contract_storage_rw::<DefaultEnvironment>(&contract).is_err()
+        );
+        Ok(())
+    })
+}
+
+#[test]
+#[cfg(feature = "ink-unstable-ecdsa")]
+fn ecdsa_recover_works() {
+    // Signed by the secret key `1` so that the public key is the generator point.
+    let signature = [
+        161, 234, 203, 74, 147, 96, 51, 212, 5, 174, 231, 9, 142, 48, 137, 201, 162, 118,
+        192, 67, 239, 16, 71, 216, 125, 86, 167, 139, 70, 7, 86, 241, 33, 87, 154, 251,
+        81, 29, 160, 4, 176, 239, 88, 211, 244, 232, 232, 52, 211, 234, 100, 115, 230,
+        47, 80, 44, 152, 166, 62, 50, 8, 13, 86, 175, 28,
+    ];
+    let message_hash = [
+        162, 28, 244, 179, 96, 76, 244, 178, 188, 83, 230, 248, 143, 106, 77, 117, 239,
+        95, 244, 171, 65, 95, 62, 153, 174, 166, 182, 28, 130, 73, 196, 208,
+    ];
+    const EXPECTED_PUBLIC_KEY: [u8; 33] = [
+        2, 121, 190, 102, 126, 249, 220, 187, 172, 85, 160, 98, 149, 206, 135, 11, 7, 2,
+        155, 252, 219, 45, 206, 40, 217, 89, 242, 129, 91, 22, 248, 23, 152,
+    ];
+    let mut output = [0x00; 33];
+    assert_eq!(
+        crate::ecdsa_recover(&signature, &message_hash, &mut output),
+        Ok(())
+    );
+    assert_eq!(output, EXPECTED_PUBLIC_KEY);
+    // The recovery id `28` used by Ethereum equals the recovery id `1`.
+    let mut signature_1 = signature;
+    signature_1[64] = 1;
+    let mut output_1 = [0x00; 33];
+    assert_eq!(
+        crate::ecdsa_recover(&signature_1, &message_hash, &mut output_1),
+        Ok(())
+    );
+    assert_eq!(output_1, EXPECTED_PUBLIC_KEY);
+}
+
+#[test]
+#[cfg(feature = "ink-unstable-ecdsa")]
+fn ecdsa_recover_yields_odd_public_key() {
+    // The SHA2-256 hash of `ink!` signed with recovery id `0`.
+    let signature = [
+        140, 16, 96, 113, 52, 200, 127, 226, 91, 25, 110, 76, 200, 79, 75, 1, 191, 126,
+        234, 149, 190, 219, 194, 180, 143, 227, 251, 36, 25, 201, 171, 69, 103, 75, 78,
+        33, 108, 85, 136, 72, 192, 144, 91, 159, 249, 176, 197, 9, 148, 155, 42, 148, 38,
+        91, 104, 79, 37, 144, 40, 13, 197, 108, 131, 62, 0,
+    ];
+    let mut message_hash = [0x00; 32];
+    crate::hash_bytes::<crate::hash::Sha2x256>(b"ink!", &mut message_hash);
+    let mut output = [0x00; 33];
+    assert_eq!(
+        crate::ecdsa_recover(&signature, &message_hash, &mut output),
+        Ok(())
+    );
+    assert_eq!(
+        output,
+        [
+            3, 183, 73, 168, 25, 70, 208, 33, 246, 50, 46, 218, 218, 208, 154, 25, 103,
+            80, 47, 13, 47, 59, 215, 100, 51, 0, 245, 114, 82, 147, 128, 194, 23
+        ]
+    );
+}
+
+#[test]
+#[cfg(feature = "ink-unstable-ecdsa")]
+fn ecdsa_recover_fails_for_invalid_signature() {
+    let message_hash = [0x42; 32];
+    let mut output = [0x00; 33];
+    // Both `r` and `s` must be non-zero.
+    assert_eq!(
+        crate::ecdsa_recover(&[0x00; 65], &message_hash, &mut output),
+        Err(crate::Error::EcdsaRecoverFailed)
+    );
+    // The recovery id must be within `0..=3` or `27..=30`.
+    let mut signature = [0x01; 65];
+    signature[64] = 4;
+    assert_eq!(
+        crate::ecdsa_recover(&signature, &message_hash, &mut output),
+        Err(crate::Error::EcdsaRecoverFailed)
+    );
+    assert_eq!(output, [0x00; 33]);
+}
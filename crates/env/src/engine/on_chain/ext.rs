@@ -76,6 +76,9 @@ define_error_codes! {
     CodeNotFound = 7,
     /// The account that was called is either no contract (e.g. user account) or is a tombstone.
     NotCallable = 8,
+    /// ECDSA public key recovery failed. Most probably the signature is invalid.
+    /// Uses the same discriminant as the `ReturnCode` of the contracts pallet.
+    EcdsaRecoverFailed = 11,
 }
 
 /// Thin-wrapper around a `u32` representing a pointer for Wasm32.
@@ -243,6 +246,14 @@ mod sys {
         ) -> ReturnCode;
         pub fn seal_clear_storage(key_ptr: Ptr32<[u8]>);
 
+        #[cfg(feature = "ink-unstable-runtime-storage")]
+        pub fn seal_get_runtime_storage(
+            key_ptr: Ptr32<[u8]>,
+            key_len: u32,
+            output_ptr: Ptr32Mut<[u8]>,
+            output_len_ptr: Ptr32Mut<u32>,
+        ) -> ReturnCode;
+
         pub fn seal_restore_to(
             dest_ptr: Ptr32<[u8]>,
             dest_len: u32,
@@ -308,6 +319,13 @@ mod sys {
         );
         pub fn seal_println(str_ptr: Ptr32<[u8]>, str_len: u32);
 
+        #[cfg(feature = "ink-unstable-ecdsa")]
+        pub fn seal_ecdsa_recover(
+            signature_ptr: Ptr32<[u8]>,
+            message_hash_ptr: Ptr32<[u8]>,
+            output_ptr: Ptr32Mut<[u8]>,
+        ) -> ReturnCode;
+
         pub fn seal_hash_keccak_256(
             input_ptr: Ptr32<[u8]>,
             input_len: u32,
@@ -448,6 +466,23 @@ pub fn get_storage(key: &[u8], output: &mut &mut [u8]) -> Result {
     ret_code.into()
 }
 
+#[cfg(feature = "ink-unstable-runtime-storage")]
+pub fn get_runtime_storage(key: &[u8], output: &mut &mut [u8]) -> Result {
+    let mut output_len = output.len() as u32;
+    let ret_code = {
+        unsafe {
+            sys::seal_get_runtime_storage(
+                Ptr32::from_slice(key),
+                key.len() as u32,
+                Ptr32Mut::from_slice(output),
+                Ptr32Mut::from_ref(&mut output_len),
+            )
+        }
+    };
+    extract_from_slice(output, output_len as usize);
+    ret_code.into()
+}
+
 /// Restores a tombstone to the original smart contract.
 ///
 /// # Params
@@ -618,3 +653,19 @@ impl_hash_fn!(sha2_256, 32);
 impl_hash_fn!(keccak_256, 32);
 impl_hash_fn!(blake2_256, 32);
 impl_hash_fn!(blake2_128, 16);
+
+#[cfg(feature = "ink-unstable-ecdsa")]
+pub fn ecdsa_recover(
+    signature: &[u8; 65],
+    message_hash: &[u8; 32],
+    output: &mut [u8; 33],
+) -> Result {
+    let ret_code = unsafe {
+        sys::seal_ecdsa_recover(
+            Ptr32::from_slice(signature),
+            Ptr32::from_slice(message_hash),
+            Ptr32Mut::from_slice(output),
+        )
+    };
+    ret_code.into()
+}
@@ -13,6 +13,7 @@
 // limitations under the License.
 
 use super::{
+    super::twox,
     ext,
     EnvInstance,
     Error as ExtError,
@@ -20,7 +21,6 @@ use super::{
 };
 use crate::{
     call::{
-        utils::ReturnType,
         CallParams,
         CreateParams,
     },
@@ -31,6 +31,8 @@ use crate::{
         HashOutput,
         Keccak256,
         Sha2x256,
+        Twox128,
+        Twox256,
     },
     topics::{
         Topics,
@@ -94,6 +96,30 @@ impl CryptoHash for Keccak256 {
     }
 }
 
+impl CryptoHash for Twox128 {
+    fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type) {
+        type OutputType = [u8; 16];
+        static_assertions::assert_type_eq_all!(
+            <Twox128 as HashOutput>::Type,
+            OutputType
+        );
+        let output: &mut OutputType = arrayref::array_mut_ref!(output, 0, 16);
+        twox::twox_128(input, output);
+    }
+}
+
+impl CryptoHash for Twox256 {
+    fn hash(input: &[u8], output: &mut <Self as HashOutput>::Type) {
+        type OutputType = [u8; 32];
+        static_assertions::assert_type_eq_all!(
+            <Twox256 as HashOutput>::Type,
+            OutputType
+        );
+        let output: &mut OutputType = arrayref::array_mut_ref!(output, 0, 32);
+        twox::twox_256(input, output);
+    }
+}
+
 impl From<ext::Error> for Error {
     fn from(ext_error: ext::Error) -> Self {
         match ext_error {
@@ -106,6 +132,7 @@ impl From<ext::Error> for Error {
             ext::Error::NewContractNotFunded => Self::NewContractNotFunded,
             ext::Error::CodeNotFound => Self::CodeNotFound,
             ext::Error::NotCallable => Self::NotCallable,
+            ext::Error::EcdsaRecoverFailed => Self::EcdsaRecoverFailed,
         }
     }
 }
@@ -181,7 +208,7 @@ impl EnvInstance {
     }
 
     /// Reusable implementation for invoking another contract message.
-    fn invoke_contract_impl<T, Args, RetType, R>(
+    pub(super) fn invoke_contract_impl<T, Args, RetType, R>(
         &mut self,
         params: &CallParams<T, Args, RetType>,
     ) -> Result<R>
@@ -235,6 +262,21 @@ impl EnvBackend for EnvInstance {
         ext::clear_storage(key.as_bytes())
     }
 
+    #[cfg(feature = "ink-unstable-runtime-storage")]
+    fn get_runtime_storage<R>(&mut self, key: &[u8]) -> Result<Option<R>>
+    where
+        R: scale::Decode,
+    {
+        let output = &mut self.scoped_buffer().take_rest();
+        match ext::get_runtime_storage(key, output) {
+            Ok(_) => (),
+            Err(ExtError::KeyNotFound) => return Ok(None),
+            Err(_) => panic!("encountered unexpected error"),
+        }
+        let decoded = scale::Decode::decode(&mut &output[..])?;
+        Ok(Some(decoded))
+    }
+
     fn decode_input<T>(&mut self) -> Result<T>
     where
         T: scale::Decode,
@@ -272,6 +314,16 @@ impl EnvBackend for EnvInstance {
         <H as CryptoHash>::hash(enc_input, output)
     }
 
+    #[cfg(feature = "ink-unstable-ecdsa")]
+    fn ecdsa_recover(
+        &mut self,
+        signature: &[u8; 65],
+        message_hash: &[u8; 32],
+        output: &mut [u8; 33],
+    ) -> Result<()> {
+        ext::ecdsa_recover(signature, message_hash, output).map_err(Into::into)
+    }
+
     #[cfg(feature = "ink-unstable-chain-extensions")]
     fn call_chain_extension<I, O>(&mut self, func_id: u32, input: &I) -> Result<O>
     where
@@ -346,29 +398,6 @@ impl TypedEnvBackend for EnvInstance {
         ext::set_rent_allowance(&buffer[..])
     }
 
-    fn invoke_contract<T, Args>(
-        &mut self,
-        call_params: &CallParams<T, Args, ()>,
-    ) -> Result<()>
-    where
-        T: Environment,
-        Args: scale::Encode,
-    {
-        self.invoke_contract_impl(call_params)
-    }
-
-    fn eval_contract<T, Args, R>(
-        &mut self,
-        call_params: &CallParams<T, Args, ReturnType<R>>,
-    ) -> Result<R>
-    where
-        T: Environment,
-        Args: scale::Encode,
-        R: scale::Decode,
-    {
-        self.invoke_contract_impl(call_params)
-    }
-
     fn instantiate_contract<T, Args, C>(
         &mut self,
         params: &CreateParams<T, Args, C>,
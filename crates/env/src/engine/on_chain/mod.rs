@@ -24,6 +24,14 @@ use self::{
     ext::Error,
 };
 use super::OnInstance;
+use crate::{
+    call::{
+        utils::ReturnType,
+        CallParams,
+    },
+    Environment,
+    Result,
+};
 
 /// The on-chain environment.
 pub struct EnvInstance {
@@ -50,3 +58,28 @@ impl OnInstance for EnvInstance {
         f(unsafe { &mut INSTANCE })
     }
 }
+
+/// Invokes the contract at the callee of the given call parameters.
+pub fn invoke_contract<T, Args>(call_params: &CallParams<T, Args, ()>) -> Result<()>
+where
+    T: Environment,
+    Args: scale::Encode,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.invoke_contract_impl(call_params)
+    })
+}
+
+/// Evaluates the contract at the callee of the given call parameters.
+pub fn eval_contract<T, Args, R>(
+    call_params: &CallParams<T, Args, ReturnType<R>>,
+) -> Result<R>
+where
+    T: Environment,
+    Args: scale::Encode,
+    R: scale::Decode,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.invoke_contract_impl(call_params)
+    })
+}
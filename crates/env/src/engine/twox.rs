@@ -0,0 +1,123 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of the non-cryptographic TwoX hashes used by Substrate.
+//!
+//! The contracts pallet does not provide host functions for them so they are
+//! computed within the contract in both the on-chain and off-chain environments.
+//!
+//! TwoX hashes concatenate the little endian encoded 64-bit xxHash outputs for
+//! the consecutive seeds `0, 1, ..` until the desired output length is reached.
+
+const PRIME64_1: u64 = 0x9E37_79B1_85EB_CA87;
+const PRIME64_2: u64 = 0xC2B2_AE3D_27D4_EB4F;
+const PRIME64_3: u64 = 0x1656_67B1_9E37_79F9;
+const PRIME64_4: u64 = 0x85EB_CA77_C2B2_AE63;
+const PRIME64_5: u64 = 0x27D4_EB2F_1656_67C5;
+
+/// Reads the first 8 bytes of the input as little endian `u64`.
+fn read_u64(input: &[u8]) -> u64 {
+    u64::from_le_bytes(*arrayref::array_ref!(input, 0, 8))
+}
+
+/// Reads the first 4 bytes of the input as little endian `u32`.
+fn read_u32(input: &[u8]) -> u32 {
+    u32::from_le_bytes(*arrayref::array_ref!(input, 0, 4))
+}
+
+/// Processes a single 8 bytes lane into the accumulator.
+fn round(acc: u64, lane: u64) -> u64 {
+    acc.wrapping_add(lane.wrapping_mul(PRIME64_2))
+        .rotate_left(31)
+        .wrapping_mul(PRIME64_1)
+}
+
+/// Merges one of the four accumulators into the hash.
+fn merge_round(hash: u64, acc: u64) -> u64 {
+    (hash ^ round(0, acc))
+        .wrapping_mul(PRIME64_1)
+        .wrapping_add(PRIME64_4)
+}
+
+/// Computes the 64-bit xxHash of the input using the given seed.
+fn xxh64(input: &[u8], seed: u64) -> u64 {
+    let mut rest = input;
+    let mut hash = if input.len() >= 32 {
+        let mut acc = [
+            seed.wrapping_add(PRIME64_1).wrapping_add(PRIME64_2),
+            seed.wrapping_add(PRIME64_2),
+            seed,
+            seed.wrapping_sub(PRIME64_1),
+        ];
+        while rest.len() >= 32 {
+            for (n, acc) in acc.iter_mut().enumerate() {
+                *acc = round(*acc, read_u64(&rest[n * 8..]));
+            }
+            rest = &rest[32..];
+        }
+        let hash = acc[0]
+            .rotate_left(1)
+            .wrapping_add(acc[1].rotate_left(7))
+            .wrapping_add(acc[2].rotate_left(12))
+            .wrapping_add(acc[3].rotate_left(18));
+        acc.iter().fold(hash, |hash, acc| merge_round(hash, *acc))
+    } else {
+        seed.wrapping_add(PRIME64_5)
+    };
+    hash = hash.wrapping_add(input.len() as u64);
+    while rest.len() >= 8 {
+        hash ^= round(0, read_u64(rest));
+        hash = hash
+            .rotate_left(27)
+            .wrapping_mul(PRIME64_1)
+            .wrapping_add(PRIME64_4);
+        rest = &rest[8..];
+    }
+    if rest.len() >= 4 {
+        hash ^= (read_u32(rest) as u64).wrapping_mul(PRIME64_1);
+        hash = hash
+            .rotate_left(23)
+            .wrapping_mul(PRIME64_2)
+            .wrapping_add(PRIME64_3);
+        rest = &rest[4..];
+    }
+    for byte in rest {
+        hash ^= (*byte as u64).wrapping_mul(PRIME64_5);
+        hash = hash.rotate_left(11).wrapping_mul(PRIME64_1);
+    }
+    // Final avalanche of the hash bits.
+    hash ^= hash >> 33;
+    hash = hash.wrapping_mul(PRIME64_2);
+    hash ^= hash >> 29;
+    hash = hash.wrapping_mul(PRIME64_3);
+    hash ^= hash >> 32;
+    hash
+}
+
+/// Helper routine implementing variable size TwoX hash computation.
+fn twox_var(input: &[u8], output: &mut [u8]) {
+    for (seed, chunk) in output.chunks_mut(8).enumerate() {
+        chunk.copy_from_slice(&xxh64(input, seed as u64).to_le_bytes());
+    }
+}
+
+/// Conduct the TwoX 128-bit hash and place the result into `output`.
+pub fn twox_128(input: &[u8], output: &mut [u8; 16]) {
+    twox_var(input, output)
+}
+
+/// Conduct the TwoX 256-bit hash and place the result into `output`.
+pub fn twox_256(input: &[u8], output: &mut [u8; 32]) {
+    twox_var(input, output)
+}
@@ -44,6 +44,8 @@ pub enum Error {
     CodeNotFound,
     /// The account that was called is either no contract (e.g. user account) or is a tombstone.
     NotCallable,
+    /// The public key could not be recovered from the ECDSA signature.
+    EcdsaRecoverFailed,
     /// An unknown error has occured.
     UnknownError,
 }
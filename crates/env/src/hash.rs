@@ -44,6 +44,19 @@ pub enum Blake2x256 {}
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub enum Blake2x128 {}
 
+/// The TwoX hash with 128-bit output.
+///
+/// This is not a cryptographic hash and must only be used with trusted inputs.
+/// It is provided for compatibility with Substrate storage key construction.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Twox128 {}
+
+/// The TwoX hash with 256-bit output.
+///
+/// This is not a cryptographic hash and must only be used with trusted inputs.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Twox256 {}
+
 mod private {
     /// Seals the implementation of `CryptoHash` and `HashOutput`.
     pub trait Sealed {}
@@ -53,6 +66,8 @@ impl private::Sealed for Sha2x256 {}
 impl private::Sealed for Keccak256 {}
 impl private::Sealed for Blake2x256 {}
 impl private::Sealed for Blake2x128 {}
+impl private::Sealed for Twox128 {}
+impl private::Sealed for Twox256 {}
 
 impl HashOutput for Sha2x256 {
     type Type = [u8; 32];
@@ -69,3 +84,11 @@ impl HashOutput for Blake2x256 {
 impl HashOutput for Blake2x128 {
     type Type = [u8; 16];
 }
+
+impl HashOutput for Twox128 {
+    type Type = [u8; 16];
+}
+
+impl HashOutput for Twox256 {
+    type Type = [u8; 32];
+}
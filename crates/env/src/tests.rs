@@ -53,6 +53,28 @@ fn test_hash_blake2_256() {
     );
 }
 
+#[test]
+fn test_hash_blake2_256_known_vectors() {
+    // Reference outputs of BLAKE2b with a 256-bit digest.
+    let mut output = [0x00_u8; 32];
+    crate::hash_bytes::<crate::hash::Blake2x256>(b"", &mut output);
+    assert_eq!(
+        output,
+        [
+            14, 87, 81, 192, 38, 229, 67, 178, 232, 171, 46, 176, 96, 153, 218, 161,
+            209, 229, 223, 71, 119, 143, 119, 135, 250, 171, 69, 205, 241, 47, 227, 168
+        ]
+    );
+    crate::hash_bytes::<crate::hash::Blake2x256>(b"abc", &mut output);
+    assert_eq!(
+        output,
+        [
+            189, 221, 129, 60, 99, 66, 57, 114, 49, 113, 239, 63, 238, 152, 87, 155,
+            148, 150, 78, 59, 177, 203, 62, 66, 114, 98, 200, 192, 104, 213, 35, 25
+        ]
+    );
+}
+
 #[test]
 fn test_hash_blake2_128() {
     let mut output = [0x00_u8; 16];
@@ -62,3 +84,59 @@ fn test_hash_blake2_128() {
         [180, 158, 48, 21, 171, 163, 217, 175, 145, 160, 25, 159, 213, 142, 103, 242]
     );
 }
+
+#[test]
+fn test_hash_twox_128() {
+    let mut output = [0x00_u8; 16];
+    crate::hash_bytes::<crate::hash::Twox128>(TEST_INPUT, &mut output);
+    assert_eq!(
+        output,
+        [184, 90, 166, 82, 206, 121, 53, 220, 214, 51, 21, 244, 158, 99, 210, 59]
+    );
+}
+
+#[test]
+fn test_hash_twox_128_substrate_storage_prefix() {
+    // The storage prefix of `Balances::TotalIssuance` in Substrate.
+    let mut pallet = [0x00_u8; 16];
+    let mut item = [0x00_u8; 16];
+    crate::hash_bytes::<crate::hash::Twox128>(b"Balances", &mut pallet);
+    crate::hash_bytes::<crate::hash::Twox128>(b"TotalIssuance", &mut item);
+    assert_eq!(
+        [pallet, item].concat(),
+        [
+            0xc2, 0x26, 0x12, 0x76, 0xcc, 0x9d, 0x1f, 0x85, 0x98, 0xea, 0x4b, 0x6a,
+            0x74, 0xb1, 0x5c, 0x2f, 0x57, 0xc8, 0x75, 0xe4, 0xcf, 0xf7, 0x41, 0x48,
+            0xe4, 0x62, 0x8f, 0x26, 0x4b, 0x97, 0x4c, 0x80
+        ]
+    );
+}
+
+#[test]
+fn test_hash_twox_256() {
+    let mut output = [0x00_u8; 32];
+    crate::hash_bytes::<crate::hash::Twox256>(TEST_INPUT, &mut output);
+    assert_eq!(
+        output,
+        [
+            184, 90, 166, 82, 206, 121, 53, 220, 214, 51, 21, 244, 158, 99, 210, 59,
+            173, 79, 253, 143, 224, 57, 69, 25, 254, 88, 31, 187, 27, 139, 238, 91
+        ]
+    );
+}
+
+#[test]
+fn test_hash_twox_256_long_input() {
+    let mut output = [0x00_u8; 32];
+    crate::hash_bytes::<crate::hash::Twox256>(
+        b"The quick brown fox jumps over the lazy dog",
+        &mut output,
+    );
+    assert_eq!(
+        output,
+        [
+            188, 113, 218, 31, 54, 45, 36, 11, 219, 198, 210, 218, 182, 145, 80, 223,
+            120, 100, 39, 247, 150, 175, 75, 228, 49, 236, 232, 98, 14, 13, 241, 136
+        ]
+    );
+}
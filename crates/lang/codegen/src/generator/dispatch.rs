@@ -542,6 +542,7 @@ impl Dispatch<'_> {
                 ::ink_lang::#exec_fn::<<#storage_ident as ::ink_lang::ContractEnv>::Env, #namespace<[(); #selector_id]>, _>(
                     ::ink_lang::AcceptsPayments(#accepts_payments),
                     ::ink_lang::EnablesDynamicStorageAllocator(#is_dynamic_storage_allocation_enabled),
+                    |result| ::ink_lang::is_result_err!(*result),
                     move |state: &#mut_mod #storage_ident| {
                         <#namespace<[(); #selector_id]> as ::ink_lang::#msg_trait>::CALLABLE(
                             state, #arg_inputs
@@ -619,4 +619,43 @@ mod tests {
             b"MyTrait::my_message".to_vec(),
         );
     }
+
+    #[test]
+    fn compose_selector_is_stable() {
+        // The composed selectors are part of the contract's public interface
+        // and thus must never change for the same inputs.
+        assert_compose_selector::<ir::Message, _>(
+            syn::parse_quote! {
+                #[ink(impl)]
+                impl MyStorage {}
+            },
+            syn::parse_quote! {
+                #[ink(message)]
+                fn flip(&mut self) {}
+            },
+            [0xC0, 0x96, 0xA5, 0xF3],
+        );
+        assert_compose_selector::<ir::Constructor, _>(
+            syn::parse_quote! {
+                #[ink(impl)]
+                impl MyStorage {}
+            },
+            syn::parse_quote! {
+                #[ink(constructor)]
+                fn new() -> Self {}
+            },
+            [0xD1, 0x83, 0x51, 0x2B],
+        );
+        assert_compose_selector::<ir::Message, _>(
+            syn::parse_quote! {
+                #[ink(impl)]
+                impl MyTrait for MyStorage {}
+            },
+            syn::parse_quote! {
+                #[ink(message)]
+                fn my_message(&self) {}
+            },
+            [0xEA, 0x48, 0x09, 0x33],
+        );
+    }
 }
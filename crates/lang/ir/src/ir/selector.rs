@@ -16,7 +16,10 @@
 ///
 /// # Note
 ///
-/// This is equal to the first four bytes of the SHA-3 hash of a function's name.
+/// This is equal to the first four bytes of the BLAKE2b hash of a function's
+/// name unless provided explicitly by the user. For ink! messages and constructors
+/// defined in trait implementation blocks the name is prefixed with the trait path
+/// and, if given, the namespace of the implementation block.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Selector {
     bytes: [u8; 4],
@@ -260,6 +260,9 @@ use proc_macro::TokenStream;
 ///
 ///     - An ink! message with a `&self` receiver may only read state whereas an ink! message
 ///       with a `&mut self` receiver may mutate the contract's storage.
+///     - An ink! message returning a `Result` that evaluates to `Err` reverts the
+///       contract execution. Its changes to the contract's storage are discarded
+///       while the encoded error is still returned to the caller.
 ///
 ///     **Example:**
 ///
@@ -25,6 +25,9 @@ fn compile_tests() {
     t.pass("tests/ui/pass/08-static-env.rs");
     t.pass("tests/ui/pass/09-derive-for-storage.rs");
     t.pass("tests/ui/pass/10-alias-storage-struct-impl.rs");
+    t.pass("tests/ui/pass/11-dispatch-by-selector.rs");
+    t.pass("tests/ui/pass/12-message-result-revert.rs");
+    t.pass("tests/ui/pass/13-constructor-args.rs");
 
     t.compile_fail("tests/ui/fail/C-00-constructor-self-ref.rs");
     t.compile_fail("tests/ui/fail/C-01-constructor-self-mut.rs");
@@ -0,0 +1,83 @@
+use ink_lang as ink;
+
+#[ink::contract]
+mod flipper {
+    #[ink(storage)]
+    pub struct Flipper {
+        value: bool,
+    }
+
+    impl Flipper {
+        #[ink(constructor)]
+        pub fn new(init_value: bool) -> Self {
+            Self { value: init_value }
+        }
+
+        #[ink(message)]
+        pub fn flip(&mut self) {
+            self.value = !self.value;
+        }
+
+        #[ink(message)]
+        pub fn get(&self) -> bool {
+            self.value
+        }
+    }
+}
+
+use flipper::Flipper;
+use ink_env::{
+    call::Selector,
+    test::CallData,
+    DefaultEnvironment,
+};
+use ink_lang::{
+    DispatchError,
+    DispatchMode,
+    DispatchUsingMode,
+};
+
+/// The selector of `new`: `BLAKE2b("new")[0..4]`
+const NEW: [u8; 4] = [0xD1, 0x83, 0x51, 0x2B];
+/// The selector of `flip`: `BLAKE2b("flip")[0..4]`
+const FLIP: [u8; 4] = [0xC0, 0x96, 0xA5, 0xF3];
+/// The selector of `get`: `BLAKE2b("get")[0..4]`
+const GET: [u8; 4] = [0x1E, 0x5C, 0xA4, 0x56];
+
+/// Dispatches to the contract using the given selector bytes and argument.
+fn dispatch<A>(
+    mode: DispatchMode,
+    selector: [u8; 4],
+    arg: Option<A>,
+) -> Result<(), DispatchError>
+where
+    A: scale::Encode,
+{
+    let mut call_data = CallData::new(Selector::new(selector));
+    if let Some(arg) = arg {
+        call_data.push_arg(&arg);
+    }
+    ink_env::test::set_call_data(call_data).unwrap();
+    <Flipper as DispatchUsingMode>::dispatch_using_mode(mode)
+}
+
+fn main() {
+    ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+        // Messages that are not payable deny any transferred value.
+        ink_env::test::set_value_transferred::<DefaultEnvironment>(0)?;
+        assert!(dispatch(DispatchMode::Instantiate, NEW, Some(false)).is_ok());
+        assert!(dispatch::<()>(DispatchMode::Call, FLIP, None).is_ok());
+        // Unknown selectors are rejected.
+        assert!(dispatch::<()>(DispatchMode::Call, [0x00; 4], None).is_err());
+        // Returning a value ends the contract execution which panics off-chain.
+        let result =
+            std::panic::catch_unwind(|| dispatch::<()>(DispatchMode::Call, GET, None));
+        assert!(result.is_err());
+        assert_eq!(
+            ink_env::test::recorded_return_value(),
+            Some(scale::Encode::encode(&true))
+        );
+        Ok(())
+    })
+    .unwrap()
+}
@@ -0,0 +1,110 @@
+use ink_lang as ink;
+
+#[ink::contract]
+mod checked {
+    #[ink(storage)]
+    pub struct Checked {
+        value: i32,
+    }
+
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Error {
+        Negative,
+    }
+
+    impl Checked {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self { value: 0 }
+        }
+
+        #[ink(message)]
+        pub fn set(&mut self, value: i32) -> Result<i32, Error> {
+            let old_value = self.value;
+            self.value = value;
+            if value < 0 {
+                return Err(Error::Negative)
+            }
+            Ok(old_value)
+        }
+
+        #[ink(message)]
+        pub fn get(&self) -> i32 {
+            self.value
+        }
+    }
+}
+
+use checked::{
+    Checked,
+    Error,
+};
+use ink_env::{
+    call::Selector,
+    test::CallData,
+    DefaultEnvironment,
+};
+use ink_lang::{
+    DispatchMode,
+    DispatchUsingMode,
+};
+
+/// The selector of `new`: `BLAKE2b("new")[0..4]`
+const NEW: [u8; 4] = [0xD1, 0x83, 0x51, 0x2B];
+/// The selector of `set`: `BLAKE2b("set")[0..4]`
+const SET: [u8; 4] = [0x9D, 0x32, 0x93, 0x1C];
+/// The selector of `get`: `BLAKE2b("get")[0..4]`
+const GET: [u8; 4] = [0x1E, 0x5C, 0xA4, 0x56];
+
+/// Calls the contract with the given call data.
+///
+/// Returns `true` if the call ended by returning a value which panics off-chain.
+fn call(call_data: CallData) -> bool {
+    ink_env::test::set_call_data(call_data).unwrap();
+    std::panic::catch_unwind(|| {
+        <Checked as DispatchUsingMode>::dispatch_using_mode(DispatchMode::Call)
+    })
+    .is_err()
+}
+
+fn set(value: i32) -> bool {
+    let mut call_data = CallData::new(Selector::new(SET));
+    call_data.push_arg(&value);
+    call(call_data)
+}
+
+fn get() -> i32 {
+    assert!(call(CallData::new(Selector::new(GET))));
+    let output = ink_env::test::recorded_return_value().unwrap();
+    <i32 as scale::Decode>::decode(&mut &output[..]).unwrap()
+}
+
+fn main() {
+    ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+        // Messages that are not payable deny any transferred value.
+        ink_env::test::set_value_transferred::<DefaultEnvironment>(0)?;
+        ink_env::test::set_call_data(CallData::new(Selector::new(NEW)))?;
+        assert!(
+            <Checked as DispatchUsingMode>::dispatch_using_mode(DispatchMode::Instantiate)
+                .is_ok()
+        );
+        // Returning `Ok` returns normally and persists the changes.
+        assert!(set(42));
+        assert_eq!(
+            ink_env::test::recorded_return_value(),
+            Some(scale::Encode::encode(&Ok::<i32, Error>(0)))
+        );
+        assert_eq!(ink_env::test::recorded_revert_data(), None);
+        assert_eq!(get(), 42);
+        // Returning `Err` reverts the execution but still returns the error.
+        assert!(set(-1));
+        assert_eq!(
+            ink_env::test::recorded_revert_data(),
+            Some(scale::Encode::encode(&Err::<i32, Error>(Error::Negative)))
+        );
+        assert_eq!(get(), 42);
+        Ok(())
+    })
+    .unwrap()
+}
@@ -0,0 +1,81 @@
+use ink_lang as ink;
+
+#[ink::contract]
+mod counter {
+    #[ink(storage)]
+    pub struct Counter {
+        value: i32,
+        owner: AccountId,
+    }
+
+    impl Counter {
+        #[ink(constructor)]
+        pub fn new(init_value: i32, owner: AccountId) -> Self {
+            Self {
+                value: init_value,
+                owner,
+            }
+        }
+
+        #[ink(message)]
+        pub fn get(&self) -> (i32, AccountId) {
+            (self.value, self.owner)
+        }
+    }
+}
+
+use counter::Counter;
+use ink_env::{
+    call::Selector,
+    test::CallData,
+    AccountId,
+    DefaultEnvironment,
+};
+use ink_lang::{
+    DispatchMode,
+    DispatchUsingMode,
+};
+
+/// The selector of `new`: `BLAKE2b("new")[0..4]`
+const NEW: [u8; 4] = [0xD1, 0x83, 0x51, 0x2B];
+/// The selector of `get`: `BLAKE2b("get")[0..4]`
+const GET: [u8; 4] = [0x1E, 0x5C, 0xA4, 0x56];
+
+fn main() {
+    ink_env::test::run_test::<DefaultEnvironment, _>(|_| {
+        // Messages that are not payable deny any transferred value.
+        ink_env::test::set_value_transferred::<DefaultEnvironment>(0)?;
+        let owner = AccountId::from([0x42; 32]);
+        // The constructor arguments follow the selector in the input.
+        let mut call_data = CallData::new(Selector::new(NEW));
+        call_data.push_arg(&-7i32);
+        call_data.push_arg(&owner);
+        ink_env::test::set_call_data(call_data)?;
+        assert!(
+            <Counter as DispatchUsingMode>::dispatch_using_mode(DispatchMode::Instantiate)
+                .is_ok()
+        );
+        // Missing constructor arguments are rejected.
+        let mut call_data = CallData::new(Selector::new(NEW));
+        call_data.push_arg(&-7i32);
+        ink_env::test::set_call_data(call_data)?;
+        assert!(
+            <Counter as DispatchUsingMode>::dispatch_using_mode(DispatchMode::Instantiate)
+                .is_err()
+        );
+        // Query the initialized state.
+        //
+        // Returning a value ends the contract execution which panics off-chain.
+        ink_env::test::set_call_data(CallData::new(Selector::new(GET)))?;
+        let result = std::panic::catch_unwind(|| {
+            <Counter as DispatchUsingMode>::dispatch_using_mode(DispatchMode::Call)
+        });
+        assert!(result.is_err());
+        assert_eq!(
+            ink_env::test::recorded_return_value(),
+            Some(scale::Encode::encode(&(-7i32, owner)))
+        );
+        Ok(())
+    })
+    .unwrap()
+}
@@ -95,13 +95,17 @@ impl From<EnablesDynamicStorageAllocator> for bool {
 ///
 /// # Note
 ///
-/// The closure is supposed to already contain all the arguments that the real
-/// message requires and forwards them.
+/// - The closure is supposed to already contain all the arguments that the real
+///   message requires and forwards them.
+/// - The `is_err` function tells whether the message output is a `Result::Err`
+///   in which case the contract execution is reverted while still returning
+///   the encoded output to the caller.
 #[inline]
 #[doc(hidden)]
 pub fn execute_message<E, M, F>(
     accepts_payments: AcceptsPayments,
     enables_dynamic_storage_allocator: EnablesDynamicStorageAllocator,
+    is_err: fn(&<M as FnOutput>::Output) -> bool,
     f: F,
 ) -> Result<()>
 where
@@ -121,12 +125,11 @@ where
     let root_key = Key::from([0x00; 32]);
     let state = ManuallyDrop::new(pull_spread_root::<<M as FnState>::State>(&root_key));
     let result = f(&state);
-    if enables_dynamic_storage_allocator {
+    let is_reverted = is_err(&result);
+    if enables_dynamic_storage_allocator && !is_reverted {
         alloc::finalize();
     }
-    if TypeId::of::<<M as FnOutput>::Output>() != TypeId::of::<()>() {
-        ink_env::return_value::<<M as FnOutput>::Output>(ReturnFlags::default(), &result)
-    }
+    return_message_output::<M>(is_reverted, &result);
     Ok(())
 }
 
@@ -153,13 +156,18 @@ where
 ///
 /// # Note
 ///
-/// The closure is supposed to already contain all the arguments that the real
-/// message requires and forwards them.
+/// - The closure is supposed to already contain all the arguments that the real
+///   message requires and forwards them.
+/// - The `is_err` function tells whether the message output is a `Result::Err`
+///   in which case the contract execution is reverted while still returning
+///   the encoded output to the caller. Changes to the contract storage are not
+///   pushed in this case.
 #[inline]
 #[doc(hidden)]
 pub fn execute_message_mut<E, M, F>(
     accepts_payments: AcceptsPayments,
     enables_dynamic_storage_allocator: EnablesDynamicStorageAllocator,
+    is_err: fn(&<M as FnOutput>::Output) -> bool,
     f: F,
 ) -> Result<()>
 where
@@ -180,14 +188,35 @@ where
     let mut state =
         ManuallyDrop::new(pull_spread_root::<<M as FnState>::State>(&root_key));
     let result = f(&mut state);
-    push_spread_root::<<M as FnState>::State>(&state, &root_key);
-    if enables_dynamic_storage_allocator {
-        alloc::finalize();
+    let is_reverted = is_err(&result);
+    if !is_reverted {
+        push_spread_root::<<M as FnState>::State>(&state, &root_key);
+        if enables_dynamic_storage_allocator {
+            alloc::finalize();
+        }
     }
+    return_message_output::<M>(is_reverted, &result);
+    Ok(())
+}
+
+/// Returns the output of the message to the caller.
+///
+/// Reverts the contract execution if `is_reverted` is `true`.
+///
+/// # Note
+///
+/// Messages returning `()` simply return normally.
+#[inline]
+fn return_message_output<M>(is_reverted: bool, result: &<M as FnOutput>::Output)
+where
+    M: FnOutput,
+{
     if TypeId::of::<<M as FnOutput>::Output>() != TypeId::of::<()>() {
-        ink_env::return_value::<<M as FnOutput>::Output>(ReturnFlags::default(), &result)
+        ink_env::return_value::<<M as FnOutput>::Output>(
+            ReturnFlags::default().set_reverted(is_reverted),
+            result,
+        )
     }
-    Ok(())
 }
 
 /// Executes the given constructor closure.
@@ -22,6 +22,9 @@ mod error;
 mod events;
 mod traits;
 
+#[doc(hidden)]
+pub mod result_info;
+
 pub use self::{
     contract::{
         DispatchMode,
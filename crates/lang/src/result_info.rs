@@ -0,0 +1,82 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Utilities to inspect the `Result` returned by an ink! message.
+//!
+//! Since ink! messages can return any encodable type, the dispatcher cannot
+//! tell generically whether a message returned an `Err`. Instead the ink!
+//! code generation inspects the concretely typed message output via
+//! [`is_result_err!`](`crate::is_result_err`) which falls back to `false`
+//! for all non-`Result` types.
+
+/// Wraps a reference to the output of an ink! message.
+#[doc(hidden)]
+pub struct IsResultErr<'lt, T>(pub &'lt T);
+
+impl<T, E> IsResultErr<'_, core::result::Result<T, E>> {
+    /// Returns `true` if the wrapped `Result` is an `Err`.
+    ///
+    /// # Note
+    ///
+    /// Inherent methods take precedence over the trait method of
+    /// [`IsResultErrFallback`] for concretely typed `Result` outputs.
+    #[inline]
+    pub fn value(&self) -> bool {
+        self.0.is_err()
+    }
+}
+
+/// Fallback for all ink! message outputs that are not of type `Result`.
+#[doc(hidden)]
+pub trait IsResultErrFallback {
+    /// Always returns `false` since the output is not a `Result`.
+    #[inline]
+    fn value(&self) -> bool {
+        false
+    }
+}
+
+impl<T> IsResultErrFallback for IsResultErr<'_, T> {}
+
+/// Evaluates to `true` if the given expression is a `Result::Err`.
+///
+/// Evaluates to `false` for `Result::Ok` and for all non-`Result` types.
+///
+/// # Note
+///
+/// The type of the expression must be known at the macro call site.
+/// In generic contexts this always evaluates to `false`.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! is_result_err {
+    ( $e:expr $(,)? ) => {{
+        #[allow(unused_imports)]
+        use $crate::result_info::IsResultErrFallback as _;
+        $crate::result_info::IsResultErr(&$e).value()
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn is_result_err_works() {
+        let ok: Result<u32, ()> = Ok(42);
+        let err: Result<u32, ()> = Err(());
+        assert!(!crate::is_result_err!(ok));
+        assert!(crate::is_result_err!(err));
+        assert!(!crate::is_result_err!(42u32));
+        assert!(!crate::is_result_err!(Some(())));
+        assert!(!crate::is_result_err!(()));
+    }
+}
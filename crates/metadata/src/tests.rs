@@ -169,3 +169,63 @@ fn spec_contract_json() {
         })
     )
 }
+
+#[test]
+fn spec_event_json() {
+    // given
+    let event = EventSpec::new("Transfer")
+        .args(vec![
+            EventParamSpec::new("to")
+                .of_type(TypeSpec::with_name_segs::<u64, _>(
+                    vec!["AccountIndex"].into_iter().map(AsRef::as_ref),
+                ))
+                .indexed(true)
+                .docs(vec!["The receiver."])
+                .done(),
+            EventParamSpec::new("value")
+                .of_type(TypeSpec::with_name_segs::<u128, _>(
+                    vec!["Balance"].into_iter().map(AsRef::as_ref),
+                ))
+                .indexed(false)
+                .done(),
+        ])
+        .docs(vec!["Emitted upon a transfer."])
+        .done();
+    let mut registry = Registry::new();
+    let compact_spec = event.into_compact(&mut registry);
+
+    // when
+    let json = serde_json::to_value(&compact_spec).unwrap();
+    let deserialized: EventSpec<CompactForm> =
+        serde_json::from_value(json.clone()).unwrap();
+
+    // then
+    assert_eq!(
+        json,
+        json!({
+            "name": "Transfer",
+            "args": [
+                {
+                    "name": "to",
+                    "indexed": true,
+                    "type": {
+                        "displayName": ["AccountIndex"],
+                        "type": 1
+                    },
+                    "docs": ["The receiver."]
+                },
+                {
+                    "name": "value",
+                    "indexed": false,
+                    "type": {
+                        "displayName": ["Balance"],
+                        "type": 2
+                    },
+                    "docs": []
+                }
+            ],
+            "docs": ["Emitted upon a transfer."]
+        })
+    );
+    assert_eq!(deserialized, compact_spec);
+}
@@ -13,11 +13,13 @@
 // limitations under the License.
 
 use core::{
+    convert::TryInto,
     fmt,
     ops::{
         Add,
         AddAssign,
     },
+    str::FromStr,
 };
 
 /// Key into contract storage.
@@ -29,6 +31,13 @@ use core::{
 ///
 /// This is the most low-level primitive to identify contract storage cells.
 ///
+/// # Arithmetic
+///
+/// Adding a `u64` offset to a key treats its 32 bytes as a little-endian
+/// 256-bit integer. Offsets beyond the top of the key space wrap around,
+/// e.g. adding `1` to the key with all bytes set to `0xFF` yields the
+/// zero key.
+///
 /// # Unsafe
 ///
 /// Prefer using high-level types found in `ink_storage` to operate on the contract
@@ -59,9 +68,59 @@ impl fmt::Debug for Key {
     }
 }
 
+/// Prints the bytes of the key as `0x`-prefixed hex string.
+///
+/// The result can be parsed back into a key using [`FromStr`].
 impl fmt::Display for Key {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.write_bytes(f)
+        write!(f, "{:#x}", self)
+    }
+}
+
+/// Prints the bytes of the key as 64 lower case hex digits.
+///
+/// The alternate flag (`{:#x}`) prefixes the output with `0x`.
+impl fmt::LowerHex for Key {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if f.alternate() {
+            write!(f, "0x")?;
+        }
+        for limb in &self.0 {
+            for byte in &limb.to_le_bytes() {
+                write!(f, "{:02x}", byte)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Error returned when parsing a [`Key`] from a string fails.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ParseKeyError;
+
+impl fmt::Display for ParseKeyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "expected exactly 64 hex digits optionally prefixed by `0x`")
+    }
+}
+
+/// Parses a key from exactly 64 hex digits optionally prefixed by `0x`.
+///
+/// Both lower and upper case hex digits are accepted.
+impl FromStr for Key {
+    type Err = ParseKeyError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.strip_prefix("0x").unwrap_or(s).as_bytes();
+        if digits.len() != 64 {
+            return Err(ParseKeyError)
+        }
+        let digit = |c: u8| (c as char).to_digit(16).ok_or(ParseKeyError);
+        let mut bytes = [0x00; 32];
+        for (byte, pair) in bytes.iter_mut().zip(digits.chunks_exact(2)) {
+            *byte = (digit(pair[0])? << 4 | digit(pair[1])?) as u8;
+        }
+        Ok(Self::from(bytes))
     }
 }
 
@@ -94,26 +153,13 @@ impl Key {
     /// little endian byte ordering.
     #[inline]
     fn from_bytes_be_fallback(bytes: [u8; 32]) -> Self {
-        #[inline]
-        fn carve_out_u64_bytes(bytes: &[u8; 32], offset: u8) -> [u8; 8] {
-            let o = (offset * 8) as usize;
-            [
-                bytes[o],
-                bytes[o + 1],
-                bytes[o + 2],
-                bytes[o + 3],
-                bytes[o + 4],
-                bytes[o + 5],
-                bytes[o + 6],
-                bytes[o + 7],
-            ]
+        let mut result = [0x00; 4];
+        for (i, chunk) in bytes.chunks_exact(8).enumerate() {
+            result[i] = u64::from_le_bytes(
+                chunk.try_into().expect("chunks are exactly 8 bytes long"),
+            );
         }
-        Self([
-            u64::from_le_bytes(carve_out_u64_bytes(&bytes, 0)),
-            u64::from_le_bytes(carve_out_u64_bytes(&bytes, 1)),
-            u64::from_le_bytes(carve_out_u64_bytes(&bytes, 2)),
-            u64::from_le_bytes(carve_out_u64_bytes(&bytes, 3)),
-        ])
+        Self(result)
     }
 
     /// Tries to return the underlying bytes as slice.
@@ -293,6 +339,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn display_works() {
+        let key = Key::from(test_bytes());
+        let expected =
+            "0x000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+        assert_eq!(format!("{}", key), expected);
+        assert_eq!(format!("{:#x}", key), expected);
+        assert_eq!(format!("{:x}", key), &expected[2..]);
+    }
+
+    #[test]
+    fn from_str_works() {
+        let key = Key::from(test_bytes());
+        assert_eq!(key.to_string().parse::<Key>(), Ok(key));
+        assert_eq!(format!("{:x}", key).parse::<Key>(), Ok(key));
+        assert_eq!(format!("{:x}", key).to_uppercase().parse::<Key>(), Ok(key));
+        for bytes in &[[0x00; 32], [0x42; 32], [0xFF; 32]] {
+            let key = Key::from(*bytes);
+            assert_eq!(key.to_string().parse::<Key>(), Ok(key));
+        }
+    }
+
+    #[test]
+    fn from_str_fails_for_invalid_input() {
+        let valid = Key::from(test_bytes()).to_string();
+        let invalid = [
+            String::new(),
+            String::from("0x"),
+            // Too few or too many digits.
+            String::from(&valid[..65]),
+            format!("{}0", valid),
+            // Non hex digits.
+            valid.replace('a', "g"),
+            valid.replacen('0', "+", 2),
+            // Prefixes other than `0x`.
+            valid.replace("0x", "0X"),
+            valid.replace("0x", "x0"),
+            // Debug representation.
+            format!("{:?}", Key::from(test_bytes())),
+        ];
+        for input in &invalid {
+            assert_eq!(input.parse::<Key>(), Err(ParseKeyError), "{}", input);
+        }
+    }
+
     #[test]
     #[rustfmt::skip]
     fn from_works() {
@@ -303,6 +394,19 @@ mod tests {
         assert_eq!(Key::from_bytes_be_fallback(test_bytes).to_bytes_be_fallback(), test_bytes);
     }
 
+    #[test]
+    fn from_bytes_be_fallback_works() {
+        assert_eq!(
+            Key::from_bytes_be_fallback(test_bytes()).0,
+            [
+                0x0706_0504_0302_0100,
+                0x0F0E_0D0C_0B0A_0908,
+                0x1716_1514_1312_1110,
+                0x1F1E_1D1C_1B1A_1918,
+            ]
+        );
+    }
+
     #[test]
     fn add_one_to_zero() {
         let bytes = [0x00; 32];
@@ -379,6 +483,40 @@ mod tests {
         assert_eq!(key.to_bytes(), expected);
     }
 
+    #[test]
+    fn add_wraps_at_the_top_of_the_key_space() {
+        let max = Key::from([0xFF; 32]);
+        let expected = |offset: u8| {
+            let mut expected = [0x00; 32];
+            expected[0] = offset;
+            Key::from(expected)
+        };
+        assert_eq!(max + 0, max);
+        assert_eq!(max + 1, expected(0));
+        assert_eq!(max + 2, expected(1));
+        assert_eq!(max + 0x43, expected(0x42));
+        let mut key = max;
+        key += 0x43;
+        assert_eq!(key, expected(0x42));
+        // `2^256 - 1 + 2^64 - 1` wraps around to `2^64 - 2`.
+        assert_eq!(max + u64::MAX, {
+            let mut expected = [0x00; 32];
+            expected[0..8].copy_from_slice(&(u64::MAX - 1).to_le_bytes());
+            Key::from(expected)
+        });
+    }
+
+    #[test]
+    fn add_agrees_with_add_assign() {
+        let base = Key::from(test_bytes());
+        for offset in &[0_u64, 1, 42, u32::MAX as u64, u64::MAX - 1, u64::MAX] {
+            let mut key = base;
+            key += *offset;
+            assert_eq!(base + *offset, key);
+            assert_eq!(base + offset, key);
+        }
+    }
+
     #[test]
     fn add_assign_to_zero() {
         for test_value in &[0_u64, 1, 42, 10_000, u32::MAX as u64, u64::MAX] {
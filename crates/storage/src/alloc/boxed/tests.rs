@@ -211,3 +211,23 @@ fn double_free_panics() {
         // the same dynamic allocation which panics.
     })
 }
+
+#[test]
+fn dropped_box_storage_is_reused() {
+    run_test(|_| {
+        let b1 = StorageBox::new(b'A');
+        let b2 = StorageBox::new(b'B');
+        let key1 = b1.key();
+        let key2 = b2.key();
+        assert_ne!(key1, key2);
+        // Dropping a box frees its dynamic allocation so that the next
+        // storage box reuses its storage instead of advancing further.
+        drop(b1);
+        let b3 = StorageBox::new(b'C');
+        assert_eq!(b3.key(), key1);
+        // Once all freed allocations are in use again new ones are handed out.
+        let b4 = StorageBox::new(b'D');
+        assert_ne!(b4.key(), key1);
+        assert_ne!(b4.key(), key2);
+    })
+}
@@ -204,3 +204,22 @@ fn test_call_setup_works() {
     })
     .unwrap();
 }
+
+#[test]
+fn allocation_keys_do_not_overlap_contiguous_regions() {
+    run_default_test(|| {
+        // Dynamic allocations are not bumped through the key space but their
+        // keys are derived by hashing. Thus allocating many of them never runs
+        // into storage regions that are laid out contiguously from a root key.
+        let root_key = Key::from([0x00; 32]);
+        let contiguous = (0..TEST_ALLOCATIONS as u64)
+            .map(|offset| root_key + offset)
+            .collect::<std::collections::BTreeSet<_>>();
+        let mut allocated = std::collections::BTreeSet::new();
+        for _ in 0..TEST_ALLOCATIONS {
+            let key = alloc().key();
+            assert!(!contiguous.contains(&key));
+            assert!(allocated.insert(key), "encountered duplicate allocation key");
+        }
+    })
+}
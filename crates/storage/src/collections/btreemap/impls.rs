@@ -0,0 +1,126 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    BTreeMap as StorageBTreeMap,
+    Iter,
+};
+use crate::traits::PackedLayout;
+use core::{
+    cmp::{
+        Eq,
+        Ord,
+        PartialEq,
+    },
+    iter::FromIterator,
+    ops,
+};
+use ink_prelude::borrow::Borrow;
+
+impl<K, V> Default for StorageBTreeMap<K, V>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, K, V, Q> ops::Index<&'a Q> for StorageBTreeMap<K, V>
+where
+    Q: Ord + ?Sized,
+    K: Borrow<Q> + Ord + Clone + PackedLayout,
+    V: PackedLayout,
+{
+    type Output = V;
+
+    fn index(&self, index: &Q) -> &Self::Output {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+impl<'a, K, V, Q> ops::IndexMut<&'a Q> for StorageBTreeMap<K, V>
+where
+    Q: Ord + ?Sized,
+    K: Borrow<Q> + Ord + Clone + PackedLayout,
+    V: PackedLayout,
+{
+    fn index_mut(&mut self, index: &Q) -> &mut Self::Output {
+        self.get_mut(index).expect("index out of bounds")
+    }
+}
+
+impl<'a, K: 'a, V: 'a> IntoIterator for &'a StorageBTreeMap<K, V>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+{
+    type Item = (&'a K, &'a V);
+    type IntoIter = Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K, V> Extend<(K, V)> for StorageBTreeMap<K, V>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        for (key, value) in iter {
+            self.insert(key, value);
+        }
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for StorageBTreeMap<K, V>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+    {
+        let mut map = StorageBTreeMap::new();
+        map.extend(iter);
+        map
+    }
+}
+
+impl<K, V> PartialEq for StorageBTreeMap<K, V>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PartialEq + PackedLayout,
+{
+    fn eq(&self, other: &Self) -> bool {
+        if self.len() != other.len() {
+            return false
+        }
+        self.iter().zip(other.iter()).all(|(lhs, rhs)| lhs == rhs)
+    }
+}
+
+impl<K, V> Eq for StorageBTreeMap<K, V>
+where
+    K: Ord + Clone + PackedLayout,
+    V: Eq + PackedLayout,
+{
+}
@@ -0,0 +1,238 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    is_beyond,
+    BTreeMap as StorageBTreeMap,
+    NodeIndex,
+};
+use crate::traits::PackedLayout;
+use core::ops::Bound;
+use ink_prelude::vec::Vec;
+
+/// Walks the nodes of a storage B-tree map in the order of their keys.
+#[derive(Debug)]
+struct Cursor<'a, K, V>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+{
+    /// The B-tree map to walk.
+    map: &'a StorageBTreeMap<K, V>,
+    /// The path from the root to the current node.
+    ///
+    /// Every entry refers to a node and the position of its next key to yield.
+    /// All keys in front of this position and their subtrees have already been
+    /// yielded.
+    stack: Vec<(NodeIndex, usize)>,
+}
+
+impl<'a, K, V> Cursor<'a, K, V>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+{
+    /// Creates a cursor pointing to the smallest key of the map.
+    fn first(map: &'a StorageBTreeMap<K, V>) -> Self {
+        let mut cursor = Self {
+            map,
+            stack: Vec::new(),
+        };
+        if let Some(root) = map.header.root {
+            cursor.descend_left(root);
+        }
+        cursor
+    }
+
+    /// Creates a cursor pointing to the smallest key within the start bound.
+    fn seek(map: &'a StorageBTreeMap<K, V>, start: Bound<&K>) -> Self {
+        let mut cursor = Self {
+            map,
+            stack: Vec::new(),
+        };
+        let mut next = map.header.root;
+        while let Some(index) = next {
+            let node = map.node(index);
+            let found = match start {
+                Bound::Included(key) | Bound::Excluded(key) => node.search(key),
+                Bound::Unbounded => Err(0),
+            };
+            next = match (found, start) {
+                (Ok(pos), Bound::Excluded(_)) => {
+                    cursor.stack.push((index, pos + 1));
+                    if !node.is_leaf() {
+                        cursor.descend_left(node.children[pos + 1]);
+                    }
+                    None
+                }
+                (Ok(pos), _) => {
+                    cursor.stack.push((index, pos));
+                    None
+                }
+                (Err(pos), _) => {
+                    cursor.stack.push((index, pos));
+                    node.children.get(pos).copied()
+                }
+            };
+        }
+        cursor
+    }
+
+    /// Pushes the path from the given node down to its smallest key.
+    fn descend_left(&mut self, mut index: NodeIndex) {
+        loop {
+            self.stack.push((index, 0));
+            match self.map.node(index).children.first() {
+                Some(&child) => index = child,
+                None => return,
+            }
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Cursor<'a, K, V>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (index, pos) = self.stack.last_mut()?;
+            let node = self.map.node(*index);
+            if *pos == node.keys.len() {
+                self.stack.pop();
+                continue
+            }
+            let item = (&node.keys[*pos], &node.values[*pos]);
+            *pos += 1;
+            if let Some(&child) = node.children.get(*pos) {
+                self.descend_left(child);
+            }
+            return Some(item)
+        }
+    }
+}
+
+/// An iterator over shared references to the elements of a storage B-tree map.
+///
+/// Yields the elements in the order of their keys.
+#[derive(Debug)]
+pub struct Iter<'a, K, V>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+{
+    /// The cursor walking the map.
+    cursor: Cursor<'a, K, V>,
+    /// The number of elements that are yet to be yielded.
+    remaining: u32,
+}
+
+impl<'a, K, V> Iter<'a, K, V>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+{
+    /// Creates a new iterator for the given storage B-tree map.
+    pub(crate) fn new(map: &'a StorageBTreeMap<K, V>) -> Self {
+        Self {
+            cursor: Cursor::first(map),
+            remaining: map.len(),
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Iter<'a, K, V>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+{
+    type Item = (&'a K, &'a V);
+
+    fn count(self) -> usize {
+        self.remaining as usize
+    }
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let item = self.cursor.next()?;
+        self.remaining -= 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, K, V> ExactSizeIterator for Iter<'a, K, V>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+{
+}
+
+/// An iterator over shared references to the elements of a storage B-tree map
+/// with keys within a range.
+///
+/// Yields the elements in the order of their keys.
+#[derive(Debug)]
+pub struct Range<'a, K, V>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+{
+    /// The cursor walking the map.
+    cursor: Cursor<'a, K, V>,
+    /// The end bound of the range.
+    end: Bound<K>,
+}
+
+impl<'a, K, V> Range<'a, K, V>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+{
+    /// Creates a new iterator over the range of the given storage B-tree map.
+    pub(crate) fn new(
+        map: &'a StorageBTreeMap<K, V>,
+        start: Bound<&K>,
+        end: Bound<K>,
+    ) -> Self {
+        Self {
+            cursor: Cursor::seek(map, start),
+            end,
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for Range<'a, K, V>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+{
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (key, value) = self.cursor.next()?;
+        if is_beyond(key, &self.end) {
+            // Do not walk any further nodes once the end of the range is reached.
+            self.cursor.stack.clear();
+            return None
+        }
+        Some((key, value))
+    }
+}
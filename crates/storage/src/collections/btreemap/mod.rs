@@ -0,0 +1,560 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A storage B-tree map that associates keys with values in sorted order.
+
+mod impls;
+mod iter;
+mod storage;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::iter::{
+    Iter,
+    Range,
+};
+use crate::{
+    collections::Stash,
+    lazy::Lazy,
+    traits::PackedLayout,
+};
+use core::{
+    borrow::Borrow,
+    cmp::Ordering,
+    ops::{
+        Bound,
+        RangeBounds,
+    },
+};
+use ink_prelude::vec::Vec;
+
+/// The index type of the nodes within a B-tree map.
+type NodeIndex = u32;
+
+/// The minimum number of children of a non-root internal node.
+const B: usize = 6;
+/// The minimum number of keys of a non-root node.
+const MIN_KEYS: usize = B - 1;
+/// The maximum number of keys of a node.
+const MAX_KEYS: usize = 2 * B - 1;
+
+/// A B-tree map operating on the contract storage.
+///
+/// Stores a mapping between keys and values ordered by their keys.
+///
+/// # Note
+///
+/// Other than the storage hash map the B-tree map allows to iterate over its
+/// elements in the order of their keys and to efficiently query ranges of keys.
+///
+/// Every node of the B-tree is stored packed in a single storage cell and holds
+/// up to 11 key/value pairs. Accessing an element therefore loads all nodes on
+/// the path from the root to the element. Users should generally prefer the
+/// storage hash map unless they require the ordering of its keys.
+#[derive(Debug)]
+pub struct BTreeMap<K, V>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+{
+    /// The header of the B-tree map.
+    header: Lazy<Header>,
+    /// The nodes of the B-tree map.
+    nodes: Stash<Node<K, V>>,
+}
+
+/// Stores general commonly required information about the storage B-tree map.
+#[derive(Debug, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+struct Header {
+    /// The index of the root node if the map is not empty.
+    root: Option<NodeIndex>,
+    /// The number of elements stored in the map.
+    len: u32,
+}
+
+/// A node of the B-tree.
+///
+/// The keys of a node are sorted. Internal nodes have exactly one more child
+/// than keys where the child at index `i` holds all keys between the keys at
+/// indices `i - 1` and `i`. Leaf nodes have no children.
+#[derive(Debug, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+struct Node<K, V> {
+    /// The sorted keys of the node.
+    keys: Vec<K>,
+    /// The values associated with the keys at the same indices.
+    values: Vec<V>,
+    /// The indices of the child nodes.
+    children: Vec<NodeIndex>,
+}
+
+impl<K, V> Node<K, V> {
+    /// Creates a new node with the given children and without keys.
+    fn new(children: Vec<NodeIndex>) -> Self {
+        Self {
+            keys: Vec::new(),
+            values: Vec::new(),
+            children,
+        }
+    }
+
+    /// Returns `true` if the node is a leaf node.
+    fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+
+    /// Returns `true` if the node cannot hold any more keys.
+    fn is_full(&self) -> bool {
+        self.keys.len() == MAX_KEYS
+    }
+
+    /// Searches the key within the node.
+    ///
+    /// Returns `Ok` with the index of the key if it is found, otherwise `Err`
+    /// with the index of the child that might contain the key.
+    fn search<Q>(&self, key: &Q) -> Result<usize, usize>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.keys.binary_search_by(|probe| probe.borrow().cmp(key))
+    }
+}
+
+impl<K, V> BTreeMap<K, V>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+{
+    /// Creates a new empty storage B-tree map.
+    pub fn new() -> Self {
+        Self {
+            header: Lazy::new(Header { root: None, len: 0 }),
+            nodes: Stash::new(),
+        }
+    }
+
+    /// Returns the number of key/value pairs stored in the map.
+    pub fn len(&self) -> u32 {
+        self.header.len
+    }
+
+    /// Returns `true` if the map is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns an iterator yielding shared references to all key/value pairs
+    /// of the map in the order of their keys.
+    ///
+    /// # Note
+    ///
+    /// Avoid unbounded iteration over big storage B-tree maps.
+    /// Prefer using methods like `Iterator::take` in order to limit the number
+    /// of yielded elements.
+    pub fn iter(&self) -> Iter<K, V> {
+        Iter::new(self)
+    }
+
+    /// Returns an iterator yielding shared references to all key/value pairs
+    /// with keys within the given range in the order of their keys.
+    ///
+    /// # Note
+    ///
+    /// - Yields no elements if the start of the range is greater than its end.
+    /// - Avoid unbounded iteration over big storage B-tree maps.
+    pub fn range<R>(&self, range: R) -> Range<K, V>
+    where
+        R: RangeBounds<K>,
+    {
+        let end = match range.end_bound() {
+            Bound::Included(key) => Bound::Included(key.clone()),
+            Bound::Excluded(key) => Bound::Excluded(key.clone()),
+            Bound::Unbounded => Bound::Unbounded,
+        };
+        Range::new(self, range.start_bound(), end)
+    }
+
+    /// Returns a shared reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type,
+    /// but `Ord` on the borrowed form must match those for the key type.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (index, pos) = self.find(key)?;
+        Some(&self.node(index).values[pos])
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    ///
+    /// The key may be any borrowed form of the map's key type,
+    /// but `Ord` on the borrowed form must match those for the key type.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let (index, pos) = self.find(key)?;
+        Some(&mut self.node_mut(index).values[pos])
+    }
+
+    /// Returns `true` if there is an entry corresponding to the key in the map.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        self.find(key).is_some()
+    }
+
+    /// Inserts a key/value pair into the map.
+    ///
+    /// Returns the previous value associated with the same key if any.
+    /// If the map did have this key present, the value is updated,
+    /// and the old value is returned. The key is not updated, though.
+    pub fn insert(&mut self, key: K, new_value: V) -> Option<V> {
+        if let Some(value) = self.get_mut(&key) {
+            return Some(core::mem::replace(value, new_value))
+        }
+        let root = match self.header.root {
+            Some(root) if self.node(root).is_full() => {
+                // Grow the tree by one level by splitting the full root.
+                let new_root = self.nodes.put(Node::new(ink_prelude::vec![root]));
+                self.split_child(new_root, 0);
+                self.header.root = Some(new_root);
+                new_root
+            }
+            Some(root) => root,
+            None => {
+                let root = self.nodes.put(Node::new(Vec::new()));
+                self.header.root = Some(root);
+                root
+            }
+        };
+        self.insert_non_full(root, key, new_value);
+        self.header.len += 1;
+        None
+    }
+
+    /// Removes the key/value pair from the map associated with the given key.
+    ///
+    /// - Returns the removed value if any.
+    ///
+    /// The key may be any borrowed form of the map's key type,
+    /// but `Ord` on the borrowed form must match those for the key type.
+    pub fn take<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        if !self.contains_key(key) {
+            // Bail out early so that the tree is not rebalanced needlessly.
+            return None
+        }
+        let root = self.header.root.expect("a non-empty map must have a root");
+        let value = self.take_from(root, key);
+        let root_node = self.node(root);
+        if root_node.keys.is_empty() {
+            // Shrink the tree by one level if the root ran out of keys.
+            self.header.root = root_node.children.first().copied();
+            self.nodes.take(root);
+        }
+        self.header.len -= 1;
+        Some(value)
+    }
+}
+
+impl<K, V> BTreeMap<K, V>
+where
+    K: Ord + Clone + PackedLayout,
+    V: PackedLayout,
+{
+    /// Returns a shared reference to the node at the given index.
+    ///
+    /// # Panics
+    ///
+    /// If there is no node at the given index.
+    fn node(&self, index: NodeIndex) -> &Node<K, V> {
+        self.nodes
+            .get(index)
+            .expect("a node index must always refer to an existing node")
+    }
+
+    /// Returns an exclusive reference to the node at the given index.
+    ///
+    /// # Panics
+    ///
+    /// If there is no node at the given index.
+    fn node_mut(&mut self, index: NodeIndex) -> &mut Node<K, V> {
+        self.nodes
+            .get_mut(index)
+            .expect("a node index must always refer to an existing node")
+    }
+
+    /// Returns the index of the node and the position within it of the key.
+    fn find<Q>(&self, key: &Q) -> Option<(NodeIndex, usize)>
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        let mut index = self.header.root?;
+        loop {
+            let node = self.node(index);
+            match node.search(key) {
+                Ok(pos) => return Some((index, pos)),
+                Err(_) if node.is_leaf() => return None,
+                Err(pos) => index = node.children[pos],
+            }
+        }
+    }
+
+    /// Splits the full child at position `pos` of the parent node in two.
+    ///
+    /// The median key of the child is moved up into the parent node.
+    fn split_child(&mut self, parent: NodeIndex, pos: usize) {
+        let child = self.node(parent).children[pos];
+        let child_node = self.node_mut(child);
+        debug_assert!(child_node.is_full());
+        let mut right = Node {
+            keys: child_node.keys.split_off(MIN_KEYS + 1),
+            values: child_node.values.split_off(MIN_KEYS + 1),
+            children: Vec::new(),
+        };
+        if !child_node.is_leaf() {
+            right.children = child_node.children.split_off(MIN_KEYS + 1);
+        }
+        let median_key = child_node.keys.pop().expect("a full node has keys");
+        let median_value = child_node.values.pop().expect("a full node has values");
+        let right = self.nodes.put(right);
+        let parent_node = self.node_mut(parent);
+        parent_node.keys.insert(pos, median_key);
+        parent_node.values.insert(pos, median_value);
+        parent_node.children.insert(pos + 1, right);
+    }
+
+    /// Inserts the new key/value pair into the subtree of the non-full node.
+    fn insert_non_full(&mut self, mut index: NodeIndex, key: K, value: V) {
+        loop {
+            let node = self.node(index);
+            let mut pos = node
+                .search(&key)
+                .expect_err("the key must not be contained in the map");
+            if node.is_leaf() {
+                let node = self.node_mut(index);
+                node.keys.insert(pos, key);
+                node.values.insert(pos, value);
+                return
+            }
+            if self.node(node.children[pos]).is_full() {
+                self.split_child(index, pos);
+                if self.node(index).keys[pos] < key {
+                    pos += 1;
+                }
+            }
+            index = self.node(index).children[pos];
+        }
+    }
+
+    /// Removes the key from the subtree of the node and returns its value.
+    ///
+    /// # Note
+    ///
+    /// The node must be the root or have more than the minimum number of keys.
+    /// The key must be contained in the subtree.
+    fn take_from<Q>(&mut self, mut index: NodeIndex, key: &Q) -> V
+    where
+        K: Borrow<Q>,
+        Q: Ord + ?Sized,
+    {
+        loop {
+            let node = self.node(index);
+            match node.search(key) {
+                Ok(pos) if node.is_leaf() => {
+                    let node = self.node_mut(index);
+                    node.keys.remove(pos);
+                    return node.values.remove(pos)
+                }
+                Ok(pos) => {
+                    let (left, right) = (node.children[pos], node.children[pos + 1]);
+                    let (key, value) = if self.node(left).keys.len() > MIN_KEYS {
+                        // Replace the key by its predecessor.
+                        self.take_last(left)
+                    } else if self.node(right).keys.len() > MIN_KEYS {
+                        // Replace the key by its successor.
+                        self.take_first(right)
+                    } else {
+                        // Merge both children and the key and remove it from there.
+                        self.merge_children(index, pos);
+                        index = left;
+                        continue
+                    };
+                    let node = self.node_mut(index);
+                    node.keys[pos] = key;
+                    return core::mem::replace(&mut node.values[pos], value)
+                }
+                Err(pos) => {
+                    debug_assert!(!node.is_leaf());
+                    index = self.prepare_child(index, pos);
+                }
+            }
+        }
+    }
+
+    /// Removes and returns the last key/value pair of the subtree of the node.
+    ///
+    /// # Note
+    ///
+    /// The node must have more than the minimum number of keys.
+    fn take_last(&mut self, mut index: NodeIndex) -> (K, V) {
+        loop {
+            let node = self.node(index);
+            if node.is_leaf() {
+                let node = self.node_mut(index);
+                let key = node.keys.pop().expect("a node must not be empty");
+                let value = node.values.pop().expect("a node must not be empty");
+                return (key, value)
+            }
+            index = self.prepare_child(index, node.children.len() - 1);
+        }
+    }
+
+    /// Removes and returns the first key/value pair of the subtree of the node.
+    ///
+    /// # Note
+    ///
+    /// The node must have more than the minimum number of keys.
+    fn take_first(&mut self, mut index: NodeIndex) -> (K, V) {
+        loop {
+            let node = self.node(index);
+            if node.is_leaf() {
+                let node = self.node_mut(index);
+                return (node.keys.remove(0), node.values.remove(0))
+            }
+            index = self.prepare_child(index, 0);
+        }
+    }
+
+    /// Ensures that the child at position `pos` of the parent has more than
+    /// the minimum number of keys so that a key can be removed from it.
+    ///
+    /// Returns the index of the node that now contains the keys of the child.
+    fn prepare_child(&mut self, parent: NodeIndex, pos: usize) -> NodeIndex {
+        let parent_node = self.node(parent);
+        let child = parent_node.children[pos];
+        let left = pos.checked_sub(1).map(|pos| parent_node.children[pos]);
+        let right = parent_node.children.get(pos + 1).copied();
+        if self.node(child).keys.len() > MIN_KEYS {
+            return child
+        }
+        let has_spare_keys = |index: Option<NodeIndex>| {
+            index
+                .map(|index| self.node(index).keys.len() > MIN_KEYS)
+                .unwrap_or(false)
+        };
+        if has_spare_keys(left) {
+            self.rotate_right(parent, pos - 1);
+            child
+        } else if has_spare_keys(right) {
+            self.rotate_left(parent, pos);
+            child
+        } else if right.is_some() {
+            self.merge_children(parent, pos);
+            child
+        } else {
+            self.merge_children(parent, pos - 1);
+            left.expect("a child without right sibling must have a left sibling")
+        }
+    }
+
+    /// Moves the last key of the child at position `pos` of the parent through
+    /// the parent into the front of the child at position `pos + 1`.
+    fn rotate_right(&mut self, parent: NodeIndex, pos: usize) {
+        let parent_node = self.node(parent);
+        let (left, right) = (parent_node.children[pos], parent_node.children[pos + 1]);
+        let left_node = self.node_mut(left);
+        let key = left_node.keys.pop().expect("a node must not be empty");
+        let value = left_node.values.pop().expect("a node must not be empty");
+        let child = left_node.children.pop();
+        let parent_node = self.node_mut(parent);
+        let key = core::mem::replace(&mut parent_node.keys[pos], key);
+        let value = core::mem::replace(&mut parent_node.values[pos], value);
+        let right_node = self.node_mut(right);
+        right_node.keys.insert(0, key);
+        right_node.values.insert(0, value);
+        if let Some(child) = child {
+            right_node.children.insert(0, child);
+        }
+    }
+
+    /// Moves the first key of the child at position `pos + 1` of the parent
+    /// through the parent onto the end of the child at position `pos`.
+    fn rotate_left(&mut self, parent: NodeIndex, pos: usize) {
+        let parent_node = self.node(parent);
+        let (left, right) = (parent_node.children[pos], parent_node.children[pos + 1]);
+        let right_node = self.node_mut(right);
+        let key = right_node.keys.remove(0);
+        let value = right_node.values.remove(0);
+        let child = if right_node.is_leaf() {
+            None
+        } else {
+            Some(right_node.children.remove(0))
+        };
+        let parent_node = self.node_mut(parent);
+        let key = core::mem::replace(&mut parent_node.keys[pos], key);
+        let value = core::mem::replace(&mut parent_node.values[pos], value);
+        let left_node = self.node_mut(left);
+        left_node.keys.push(key);
+        left_node.values.push(value);
+        if let Some(child) = child {
+            left_node.children.push(child);
+        }
+    }
+
+    /// Merges the children at positions `pos` and `pos + 1` of the parent
+    /// together with the key in between them into the child at `pos`.
+    fn merge_children(&mut self, parent: NodeIndex, pos: usize) {
+        let parent_node = self.node_mut(parent);
+        let key = parent_node.keys.remove(pos);
+        let value = parent_node.values.remove(pos);
+        let right = parent_node.children.remove(pos + 1);
+        let left = parent_node.children[pos];
+        let right_node = self
+            .nodes
+            .take(right)
+            .expect("a node index must always refer to an existing node");
+        let left_node = self.node_mut(left);
+        left_node.keys.push(key);
+        left_node.keys.extend(right_node.keys);
+        left_node.values.push(value);
+        left_node.values.extend(right_node.values);
+        left_node.children.extend(right_node.children);
+    }
+}
+
+/// Returns `true` if the key lies beyond the given end bound.
+fn is_beyond<K>(key: &K, end: &Bound<K>) -> bool
+where
+    K: Ord,
+{
+    match end {
+        Bound::Included(end) => key.cmp(end) == Ordering::Greater,
+        Bound::Excluded(end) => key.cmp(end) != Ordering::Less,
+        Bound::Unbounded => false,
+    }
+}
@@ -0,0 +1,207 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    BTreeMap as StorageBTreeMap,
+    NodeIndex,
+    MAX_KEYS,
+    MIN_KEYS,
+};
+use crate::traits::{
+    KeyPtr,
+    SpreadLayout,
+};
+use core::ops::Bound;
+use ink_primitives::Key;
+
+/// The number of keys used by the tests.
+///
+/// Large enough so that the B-tree grows several levels deep.
+const N: u32 = 211;
+
+/// Returns the keys `0..N` in a scrambled order.
+fn scrambled_keys() -> impl Iterator<Item = u32> {
+    // Since `N` is prime this visits every key in `0..N` exactly once.
+    (0..N).map(|i| (i * 37) % N)
+}
+
+/// Returns a B-tree map mapping all keys in `0..N` to their doubles.
+fn filled_btree() -> StorageBTreeMap<u32, u32> {
+    scrambled_keys().map(|key| (key, key * 2)).collect()
+}
+
+/// Asserts that the nodes of the subtree are sorted and sized correctly.
+///
+/// Returns the number of keys and the height of the subtree.
+fn check_node(
+    map: &StorageBTreeMap<u32, u32>,
+    index: NodeIndex,
+    is_root: bool,
+) -> (u32, u32) {
+    let node = map.node(index);
+    assert!(node.keys.len() <= MAX_KEYS);
+    assert!(is_root || node.keys.len() >= MIN_KEYS);
+    assert_eq!(node.keys.len(), node.values.len());
+    assert!(node.keys.windows(2).all(|pair| pair[0] < pair[1]));
+    if node.is_leaf() {
+        return (node.keys.len() as u32, 1)
+    }
+    assert_eq!(node.children.len(), node.keys.len() + 1);
+    let mut len = node.keys.len() as u32;
+    let mut heights = node.children.iter().map(|&child| {
+        let (child_len, height) = check_node(map, child, false);
+        len += child_len;
+        height
+    });
+    let height = heights.next().expect("internal nodes have children");
+    assert!(heights.all(|h| h == height), "all leaves must be on the same level");
+    (len, height + 1)
+}
+
+/// Asserts that the B-tree map upholds all of its invariants.
+fn assert_invariants(map: &StorageBTreeMap<u32, u32>) {
+    match map.header.root {
+        Some(root) => assert_eq!(check_node(map, root, true).0, map.len()),
+        None => assert_eq!(map.len(), 0),
+    }
+    assert_eq!(map.nodes.len() == 0, map.header.root.is_none());
+}
+
+/// Returns always the same `KeyPtr`.
+fn key_ptr() -> KeyPtr {
+    let root_key = Key::from([0x42; 32]);
+    KeyPtr::from(root_key)
+}
+
+#[test]
+fn new_works() {
+    let map = <StorageBTreeMap<u32, u32>>::new();
+    assert!(map.is_empty());
+    assert_eq!(map.len(), 0);
+    assert!(map.iter().next().is_none());
+    assert_eq!(map, <StorageBTreeMap<u32, u32> as Default>::default());
+}
+
+#[test]
+fn insert_and_get_works() {
+    let mut map = <StorageBTreeMap<u32, u32>>::new();
+    for key in scrambled_keys() {
+        assert_eq!(map.insert(key, key * 2), None);
+        assert_invariants(&map);
+    }
+    assert_eq!(map.len(), N);
+    for key in 0..N {
+        assert_eq!(map.get(&key), Some(&(key * 2)));
+        assert!(map.contains_key(&key));
+    }
+    assert_eq!(map.get(&N), None);
+    assert!(!map.contains_key(&N));
+}
+
+#[test]
+fn insert_existing_key_replaces_value() {
+    let mut map = filled_btree();
+    assert_eq!(map.insert(42, 0), Some(84));
+    assert_eq!(map.len(), N);
+    assert_eq!(map[&42], 0);
+    map[&42] = 1;
+    assert_eq!(map.get(&42), Some(&1));
+    assert_invariants(&map);
+}
+
+#[test]
+fn iter_yields_keys_in_order() {
+    let map = filled_btree();
+    assert_eq!(map.iter().len(), N as usize);
+    assert_eq!(map.iter().count(), N as usize);
+    let expected = (0..N).map(|key| (key, key * 2)).collect::<Vec<_>>();
+    let actual = map.iter().map(|(&k, &v)| (k, v)).collect::<Vec<_>>();
+    assert_eq!(actual, expected);
+}
+
+#[test]
+fn take_works() {
+    let mut map = filled_btree();
+    assert_eq!(map.take(&N), None);
+    for (n, key) in scrambled_keys().map(|i| (i * 11) % N).enumerate() {
+        assert_eq!(map.take(&key), Some(key * 2));
+        assert_eq!(map.take(&key), None);
+        assert_eq!(map.len(), N - n as u32 - 1);
+        assert_invariants(&map);
+    }
+    assert!(map.is_empty());
+    assert!(map.iter().next().is_none());
+}
+
+#[test]
+fn take_and_reinsert_works() {
+    let mut map = filled_btree();
+    for key in (0..N).filter(|key| key % 3 == 0) {
+        assert_eq!(map.take(&key), Some(key * 2));
+    }
+    assert_invariants(&map);
+    let expected = (0..N).filter(|key| key % 3 != 0).collect::<Vec<_>>();
+    assert_eq!(map.iter().map(|(&k, _)| k).collect::<Vec<_>>(), expected);
+    for key in (0..N).filter(|key| key % 3 == 0) {
+        assert_eq!(map.insert(key, key * 2), None);
+    }
+    assert_invariants(&map);
+    assert_eq!(map, filled_btree());
+}
+
+#[test]
+fn range_works() {
+    let map = filled_btree();
+    let keys_of = |range: (Bound<u32>, Bound<u32>)| {
+        map.range(range).map(|(&k, _)| k).collect::<Vec<_>>()
+    };
+    assert_eq!(keys_of((Bound::Unbounded, Bound::Unbounded)), (0..N).collect::<Vec<_>>());
+    for start in 0..N + 2 {
+        for end in [start, start + 1, start + 13, N - 1, N + 1].iter().copied() {
+            assert_eq!(
+                keys_of((Bound::Included(start), Bound::Excluded(end))),
+                (start..end.min(N)).collect::<Vec<_>>(),
+            );
+            assert_eq!(
+                keys_of((Bound::Excluded(start), Bound::Included(end))),
+                (start + 1..(end + 1).min(N)).collect::<Vec<_>>(),
+            );
+        }
+    }
+    assert_eq!(map.range(100..).count(), (N - 100) as usize);
+    assert_eq!(map.range(..=100).count(), 101);
+    assert_eq!(map.range(50..60).map(|(_, &v)| v).max(), Some(118));
+}
+
+#[test]
+fn range_with_start_beyond_end_is_empty() {
+    let map = filled_btree();
+    assert_eq!(map.range((Bound::Included(10), Bound::Excluded(5))).next(), None);
+    assert_eq!(map.range((Bound::Excluded(10), Bound::Included(10))).next(), None);
+}
+
+#[test]
+fn spread_layout_push_pull_works() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let map1 = filled_btree();
+        SpreadLayout::push_spread(&map1, &mut key_ptr());
+        // Load the pushed B-tree map into another instance and check that
+        // both instances are equal:
+        let map2 =
+            <StorageBTreeMap<u32, u32> as SpreadLayout>::pull_spread(&mut key_ptr());
+        assert_eq!(map1, map2);
+        assert_eq!(map2.range(10..20).count(), 10);
+        Ok(())
+    })
+}
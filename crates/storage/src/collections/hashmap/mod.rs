@@ -180,6 +180,15 @@ where
     ///
     /// # Note
     ///
+    /// - The iteration order is determined by the order in which the keys are
+    ///   stored in the underlying storage stash. It is deterministic and does
+    ///   not change in between iterations as long as the hash map is not
+    ///   mutated. Hash maps built by the same sequence of insertions and
+    ///   removals iterate in the same order regardless of their storage keys.
+    /// - Iteration has to skip the vacant entries left behind in the underlying
+    ///   storage stash by removed keys, so its cost is linear in the capacity of
+    ///   the stash rather than in the length of the hash map. Use
+    ///   [`HashMap::defrag`] in order to get rid of those vacant entries.
     /// - Avoid unbounded iteration over big storage hash maps.
     /// - Prefer using methods like `Iterator::take` in order to limit the number
     ///   of yielded elements.
@@ -276,6 +285,10 @@ where
     /// - If the map did have this key present, the value is updated,
     ///   and the old value is returned. The key is not updated, though;
     ///   this matters for types that can be `==` without being identical.
+    ///
+    /// # Panics
+    ///
+    /// If the key is not yet present and the map already stores `u32::MAX` keys.
     pub fn insert(&mut self, key: K, new_value: V) -> Option<V> {
         if let Some(occupied) = self.values.get_mut(&key) {
             // Update value, don't update key.
@@ -314,6 +327,46 @@ where
         Some(entry.value)
     }
 
+    /// Removes all key/value pairs from the hash map.
+    ///
+    /// # Note
+    ///
+    /// Since the values are stored at locations derived from their keys
+    /// every key of the hash map has to be read in order to clear its value.
+    /// The values themselves are not read. The cost of this operation is
+    /// therefore linear in the capacity of the underlying keys stash.
+    pub fn clear(&mut self) {
+        for key in self.keys.iter() {
+            self.values.put(key.to_owned(), None);
+        }
+        self.keys.clear();
+    }
+
+    /// Retains only the key/value pairs specified by the predicate.
+    ///
+    /// Removes all key/value pairs for which `f(&key, &value)` returns `false`.
+    ///
+    /// # Note
+    ///
+    /// Avoid calling this method on big storage hash maps since it visits
+    /// and loads every key/value pair of the hash map.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&K, &V) -> bool,
+    {
+        let values = &mut self.values;
+        self.keys.retain(|_, key| {
+            let entry = values
+                .get(key)
+                .expect("a key must always refer to an existing entry");
+            let keep = f(key, &entry.value);
+            if !keep {
+                values.put(key.to_owned(), None);
+            }
+            keep
+        });
+    }
+
     /// Returns a shared reference to the value corresponding to the key.
     ///
     /// The key may be any borrowed form of the map's key type,
@@ -339,6 +392,11 @@ where
     }
 
     /// Returns `true` if there is an entry corresponding to the key in the map.
+    ///
+    /// # Note
+    ///
+    /// This does not decode the associated value if it has not been loaded
+    /// from the contract storage, yet.
     pub fn contains_key<Q>(&self, key: &Q) -> bool
     where
         K: Borrow<Q>,
@@ -349,7 +407,7 @@ where
         // or prevent collisions in this hash map implementation at any level.
         // Having a collision is virtually impossible since we
         // are using a keyspace of 2^256 bit.
-        self.values.get(key).is_some()
+        self.values.contains_key(key)
     }
 
     /// Defragments storage used by the storage hash map.
@@ -365,6 +423,11 @@ where
     /// This operation might be expensive, especially for big `max_iteration`
     /// parameters. The `max_iterations` parameter can be used to limit the
     /// expensiveness for this operation and instead free up storage incrementally.
+    ///
+    /// Removed entries never slow down lookups since every key is hashed
+    /// directly to the storage cell of its value without any probing. Only the
+    /// stash of keys keeps vacant entries around which are reused by later
+    /// insertions and which this operation frees up.
     pub fn defrag(&mut self, max_iterations: Option<u32>) -> u32 {
         // This method just defrags the underlying `storage::Stash` used to
         // store the keys as it can sometimes take a lot of unused storage
@@ -503,6 +566,10 @@ where
     }
 
     /// Sets the value of the entry with the `VacantEntry`'s key, and returns a mutable reference to it.
+    ///
+    /// # Panics
+    ///
+    /// If the map already stores `u32::MAX` keys.
     pub fn insert(self, value: V) -> &'a mut V {
         // At this point we know that `key` does not yet exist in the map.
         let key_index = self.keys.put(self.key().to_owned());
@@ -12,11 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::HashMap as StorageHashMap;
+use super::{
+    Entry,
+    HashMap as StorageHashMap,
+};
 use crate::traits::{
     KeyPtr,
     SpreadLayout,
 };
+use ink_env::AccountId;
 use ink_primitives::Key;
 
 /// Returns always the same `KeyPtr`.
@@ -120,6 +124,106 @@ fn get_works() {
     assert_eq!(hmap.get_mut(&b'E'), None);
 }
 
+#[test]
+fn missing_keys_are_not_found() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        // Empty hash map.
+        let mut hmap = <StorageHashMap<u8, i32>>::new();
+        assert_eq!(hmap.get(&b'A'), None);
+        assert_eq!(hmap.take(&b'A'), None);
+        assert!(matches!(hmap.entry(b'A'), Entry::Vacant(_)));
+        // Hash map loaded from storage that does not contain the queried key.
+        let hmap = [(b'A', 1), (b'B', 2)]
+            .iter()
+            .copied()
+            .collect::<StorageHashMap<u8, i32>>();
+        push_hmap(&hmap);
+        let mut hmap = pull_hmap();
+        assert_eq!(hmap.get(&b'C'), None);
+        assert_eq!(hmap.get_mut(&b'C'), None);
+        assert_eq!(hmap.take(&b'C'), None);
+        assert!(matches!(hmap.entry(b'C'), Entry::Vacant(_)));
+        assert_eq!(hmap.len(), 2);
+        Ok(())
+    })
+}
+
+#[test]
+fn get_mut_updates_are_persisted() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let hmap = [(b'A', 1), (b'B', 2)]
+            .iter()
+            .copied()
+            .collect::<StorageHashMap<u8, i32>>();
+        push_hmap(&hmap);
+        let mut hmap = pull_hmap();
+        // Modify a value in place after loading it from storage.
+        *hmap.get_mut(&b'A').expect("key must exist") += 10;
+        assert_eq!(hmap.get(&b'A'), Some(&11));
+        // Missing and taken keys cannot be modified.
+        assert_eq!(hmap.get_mut(&b'C'), None);
+        assert_eq!(hmap.take(&b'B'), Some(2));
+        assert_eq!(hmap.get_mut(&b'B'), None);
+        push_hmap(&hmap);
+        let hmap = pull_hmap();
+        assert_eq!(hmap.len(), 1);
+        assert_eq!(hmap.get(&b'A'), Some(&11));
+        assert_eq!(hmap.get(&b'B'), None);
+        Ok(())
+    })
+}
+
+#[test]
+fn entry_or_insert_with_counts_per_account() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let alice = AccountId::from([0x01; 32]);
+        let bob = AccountId::from([0x02; 32]);
+        let mut counters = <StorageHashMap<AccountId, u32>>::new();
+        for account in &[alice, bob, alice, alice] {
+            *counters.entry(*account).or_insert_with(|| 0) += 1;
+        }
+        assert_eq!(counters.len(), 2);
+        assert_eq!(counters.get(&alice), Some(&3));
+        assert_eq!(counters.get(&bob), Some(&1));
+        // Counting continues on the counters loaded from storage.
+        SpreadLayout::push_spread(&counters, &mut key_ptr());
+        let mut counters =
+            <StorageHashMap<AccountId, u32> as SpreadLayout>::pull_spread(&mut key_ptr());
+        *counters.entry(bob).or_insert_with(|| 0) += 1;
+        assert_eq!(counters.len(), 2);
+        assert_eq!(counters.get(&alice), Some(&3));
+        assert_eq!(counters.get(&bob), Some(&2));
+        Ok(())
+    })
+}
+
+#[test]
+fn entry_loads_value_only_once() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let hmap = [(b'A', 1), (b'B', 2)]
+            .iter()
+            .copied()
+            .collect::<StorageHashMap<u8, i32>>();
+        push_hmap(&hmap);
+        let mut hmap = pull_hmap();
+        assert_eq!(hmap.values.len_cached_entries(), 0);
+        // Read-modify-write of an existing and a missing key.
+        *hmap.entry(b'A').or_insert(0) += 10;
+        *hmap.entry(b'C').or_insert(0) += 30;
+        // Only the two queried values have been loaded from the storage.
+        assert_eq!(hmap.values.len_cached_entries(), 2);
+        assert_eq!(hmap.get(&b'A'), Some(&11));
+        assert_eq!(hmap.get(&b'C'), Some(&30));
+        assert_eq!(hmap.values.len_cached_entries(), 2);
+        push_hmap(&hmap);
+        let hmap = pull_hmap();
+        assert_eq!(hmap.get(&b'A'), Some(&11));
+        assert_eq!(hmap.get(&b'B'), Some(&2));
+        assert_eq!(hmap.get(&b'C'), Some(&30));
+        Ok(())
+    })
+}
+
 #[test]
 fn insert_works() {
     let mut hmap = <StorageHashMap<u8, i32>>::new();
@@ -169,6 +273,173 @@ fn take_works() {
     assert_eq!(hmap.len(), 0);
 }
 
+#[test]
+fn clear_works() {
+    // Clearing an empty hash map is a no-op.
+    let mut hmap = <StorageHashMap<u8, i32>>::new();
+    hmap.clear();
+    assert_eq!(hmap, StorageHashMap::new());
+    // Clear a filled hash map.
+    let mut hmap = [(b'A', 1), (b'B', 2), (b'C', 3), (b'D', 4)]
+        .iter()
+        .copied()
+        .collect::<StorageHashMap<u8, i32>>();
+    assert_eq!(hmap.take(&b'B'), Some(2));
+    hmap.clear();
+    assert!(hmap.is_empty());
+    assert_eq!(hmap.len(), 0);
+    assert_eq!(hmap.iter().next(), None);
+    for key in &[b'A', b'B', b'C', b'D'] {
+        assert_eq!(hmap.get(key), None);
+        assert!(!hmap.contains_key(key));
+    }
+    // A cleared hash map can be used as usual.
+    assert_eq!(hmap.insert(b'A', 10), None);
+    assert_eq!(hmap.len(), 1);
+    assert_eq!(hmap.get(&b'A'), Some(&10));
+}
+
+#[test]
+fn clear_is_persisted() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let hmap = [(b'A', 1), (b'B', 2), (b'C', 3), (b'D', 4)]
+            .iter()
+            .copied()
+            .collect::<StorageHashMap<u8, i32>>();
+        push_hmap(&hmap);
+        let mut hmap = pull_hmap();
+        hmap.clear();
+        push_hmap(&hmap);
+        let hmap = pull_hmap();
+        assert!(hmap.is_empty());
+        assert_eq!(hmap.keys().next(), None);
+        for key in &[b'A', b'B', b'C', b'D'] {
+            assert_eq!(hmap.get(key), None);
+            assert!(!hmap.contains_key(key));
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn retain_works() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let mut hmap = [(b'A', 0), (b'B', 2), (b'C', 0), (b'D', 4), (b'E', 0)]
+            .iter()
+            .copied()
+            .collect::<StorageHashMap<u8, i32>>();
+        // Evict all interleaved zero values.
+        hmap.retain(|_, value| *value != 0);
+        assert_eq!(hmap.len(), 2);
+        assert_eq!(hmap.get(&b'B'), Some(&2));
+        assert_eq!(hmap.get(&b'D'), Some(&4));
+        for key in &[b'A', b'C', b'E'] {
+            assert_eq!(hmap.get(key), None);
+        }
+        // The surviving key/value pairs are still found after a storage round-trip.
+        push_hmap(&hmap);
+        let mut hmap = pull_hmap();
+        assert_eq!(hmap.get(&b'B'), Some(&2));
+        assert_eq!(hmap.get(&b'D'), Some(&4));
+        assert_eq!(hmap.get(&b'A'), None);
+        // The predicate can also inspect the keys.
+        hmap.retain(|key, _| *key != b'B');
+        assert_eq!(hmap.keys().copied().collect::<Vec<_>>(), vec![b'D']);
+        Ok(())
+    })
+}
+
+#[test]
+fn reinsert_after_take_does_not_duplicate_keys() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let mut hmap = <StorageHashMap<u8, i32>>::new();
+        assert_eq!(hmap.insert(b'A', 1), None);
+        assert_eq!(hmap.insert(b'B', 2), None);
+        assert_eq!(hmap.take(&b'A'), Some(1));
+        // `B` is still present so inserting it again must only update its value.
+        assert_eq!(hmap.insert(b'B', 3), Some(2));
+        assert_eq!(hmap.len(), 1);
+        assert_eq!(hmap.keys().copied().collect::<Vec<_>>(), vec![b'B']);
+        assert_eq!(hmap.get(&b'B'), Some(&3));
+        // The same holds for a hash map loaded from storage.
+        push_hmap(&hmap);
+        let mut hmap = pull_hmap();
+        assert_eq!(hmap.insert(b'B', 4), Some(3));
+        assert_eq!(hmap.len(), 1);
+        assert_eq!(hmap.keys().copied().collect::<Vec<_>>(), vec![b'B']);
+        assert_eq!(hmap.get(&b'B'), Some(&4));
+        Ok(())
+    })
+}
+
+#[test]
+#[should_panic(expected = "cannot put more elements into the storage stash")]
+fn insert_into_full_hash_map_panics() {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        // Fake the header of the keys stash of a hash map that has room for
+        // exactly one more key. The header is stored at the root key and
+        // consists of the last vacant index, the length and the number of entries.
+        let root_key = Key::from([0x42; 32]);
+        let almost_full = u32::MAX - 1;
+        ink_env::set_contract_storage(
+            &root_key,
+            &(almost_full, almost_full, almost_full),
+        );
+        // Do not drop the hash map since clearing its faked storage would
+        // iterate over all of its keys.
+        let mut hmap = core::mem::ManuallyDrop::new(pull_hmap());
+        assert_eq!(hmap.len(), almost_full);
+        assert_eq!(hmap.insert(b'A', 1), None);
+        assert_eq!(hmap.len(), u32::MAX);
+        // Overwriting an existing key still works for a full hash map.
+        assert_eq!(hmap.insert(b'A', 2), Some(1));
+        assert_eq!(hmap.len(), u32::MAX);
+        // Inserting a new key into a full hash map panics.
+        hmap.insert(b'B', 3);
+        Ok(())
+    })
+    .unwrap()
+}
+
+#[test]
+fn len_is_persisted() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let mut hmap = <StorageHashMap<u8, i32>>::new();
+        // Inserting new keys increases the length.
+        assert_eq!(hmap.insert(b'A', 1), None);
+        assert_eq!(hmap.insert(b'B', 2), None);
+        assert_eq!(hmap.insert(b'C', 3), None);
+        push_hmap(&hmap);
+        let mut hmap = pull_hmap();
+        assert_eq!(hmap.len(), 3);
+        // Overwriting an existing key does not change the length.
+        assert_eq!(hmap.insert(b'B', 20), Some(2));
+        push_hmap(&hmap);
+        let mut hmap = pull_hmap();
+        assert_eq!(hmap.len(), 3);
+        // Taking an existing key decreases the length.
+        assert_eq!(hmap.take(&b'A'), Some(1));
+        push_hmap(&hmap);
+        let mut hmap = pull_hmap();
+        assert_eq!(hmap.len(), 2);
+        // Taking a missing key does not change the length.
+        assert_eq!(hmap.take(&b'A'), None);
+        assert_eq!(hmap.take(&b'Z'), None);
+        push_hmap(&hmap);
+        let mut hmap = pull_hmap();
+        assert_eq!(hmap.len(), 2);
+        assert!(!hmap.is_empty());
+        // Taking all remaining keys leaves an empty hash map behind.
+        assert_eq!(hmap.take(&b'B'), Some(20));
+        assert_eq!(hmap.take(&b'C'), Some(3));
+        push_hmap(&hmap);
+        let hmap = pull_hmap();
+        assert_eq!(hmap.len(), 0);
+        assert!(hmap.is_empty());
+        Ok(())
+    })
+}
+
 #[test]
 fn iter_next_works() {
     let hmap = [(b'A', 1), (b'B', 2), (b'C', 3), (b'D', 4)]
@@ -243,6 +514,32 @@ fn values_next_works() {
     assert_eq!(iter.count(), 0);
 }
 
+#[test]
+fn values_mut_updates_are_persisted() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let mut hmap = [(b'A', 1), (b'B', 2), (b'C', 3), (b'D', 4)]
+            .iter()
+            .copied()
+            .collect::<StorageHashMap<u8, i32>>();
+        for value in hmap.values_mut() {
+            *value *= 2;
+        }
+        push_hmap(&hmap);
+        // The doubled values are stored under their unchanged keys.
+        let hmap2 = pull_hmap();
+        assert_eq!(hmap2.len(), 4);
+        assert_eq!(
+            hmap2.keys().copied().collect::<Vec<_>>(),
+            vec![b'A', b'B', b'C', b'D']
+        );
+        assert_eq!(hmap2.get(&b'A'), Some(&2));
+        assert_eq!(hmap2.get(&b'B'), Some(&4));
+        assert_eq!(hmap2.get(&b'C'), Some(&6));
+        assert_eq!(hmap2.get(&b'D'), Some(&8));
+        Ok(())
+    })
+}
+
 #[test]
 fn keys_next_works() {
     let hmap = [(b'A', 1), (b'B', 2), (b'C', 3), (b'D', 4)]
@@ -265,6 +562,115 @@ fn keys_next_works() {
     assert_eq!(iter.next(), None);
 }
 
+#[test]
+fn iter_skips_taken_entries() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let mut hmap = [(b'A', 1), (b'B', 2), (b'C', 3), (b'D', 4)]
+            .iter()
+            .copied()
+            .collect::<StorageHashMap<u8, i32>>();
+        assert_eq!(hmap.take(&b'A'), Some(1));
+        assert_eq!(hmap.take(&b'C'), Some(3));
+        push_hmap(&hmap);
+        let hmap = pull_hmap();
+        assert_eq!(
+            hmap.iter()
+                .map(|(key, value)| (*key, *value))
+                .collect::<Vec<_>>(),
+            vec![(b'B', 2), (b'D', 4)]
+        );
+        assert_eq!(hmap.keys().copied().collect::<Vec<_>>(), vec![b'B', b'D']);
+        assert_eq!(hmap.values().copied().collect::<Vec<_>>(), vec![2, 4]);
+        assert_eq!(hmap.iter().size_hint(), (2, Some(2)));
+        Ok(())
+    })
+}
+
+#[test]
+fn iter_enumerates_all_balances() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let alice = AccountId::from([0x01; 32]);
+        let bob = AccountId::from([0x02; 32]);
+        let charlie = AccountId::from([0x03; 32]);
+        let mut balances = <StorageHashMap<AccountId, u128>>::new();
+        balances.insert(charlie, 300);
+        balances.insert(alice, 100);
+        balances.insert(bob, 200);
+        SpreadLayout::push_spread(&balances, &mut key_ptr());
+        let balances = <StorageHashMap<AccountId, u128> as SpreadLayout>::pull_spread(
+            &mut key_ptr(),
+        );
+        let expected = vec![(alice, 100), (bob, 200), (charlie, 300)];
+        let mut holders = balances
+            .iter()
+            .map(|(account, balance)| (*account, *balance))
+            .collect::<Vec<_>>();
+        holders.sort();
+        assert_eq!(holders, expected);
+        let mut accounts = balances.keys().copied().collect::<Vec<_>>();
+        accounts.sort();
+        assert_eq!(accounts, vec![alice, bob, charlie]);
+        assert_eq!(balances.values().sum::<u128>(), 600);
+        Ok(())
+    })
+}
+
+#[test]
+fn iter_order_is_deterministic() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        // Builds a hash map using the same sequence of operations every time.
+        let build = || {
+            let mut hmap = [(b'D', 4), (b'B', 2), (b'A', 1), (b'C', 3)]
+                .iter()
+                .copied()
+                .collect::<StorageHashMap<u8, i32>>();
+            // Removing an entry and inserting a new one reuses the vacant slot.
+            assert_eq!(hmap.take(&b'B'), Some(2));
+            assert_eq!(hmap.insert(b'E', 5), None);
+            hmap
+        };
+        let expected = vec![(b'D', 4), (b'E', 5), (b'A', 1), (b'C', 3)];
+        let collect = |hmap: &StorageHashMap<u8, i32>| {
+            hmap.iter()
+                .map(|(key, value)| (*key, *value))
+                .collect::<Vec<_>>()
+        };
+        let hmap = build();
+        assert_eq!(collect(&hmap), expected);
+        assert_eq!(collect(&hmap), expected);
+        // The order is preserved when loading the hash map from the storage.
+        push_hmap(&hmap);
+        let hmap2 = pull_hmap();
+        assert_eq!(collect(&hmap2), expected);
+        // The order does not depend on the storage location either.
+        for &root_key in &[Key::from([0x01; 32]), Key::from([0x02; 32])] {
+            SpreadLayout::push_spread(&build(), &mut KeyPtr::from(root_key));
+            let hmap3 = <StorageHashMap<u8, i32> as SpreadLayout>::pull_spread(
+                &mut KeyPtr::from(root_key),
+            );
+            assert_eq!(collect(&hmap3), expected);
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn for_loop_over_references_works() {
+    let mut hmap = [(b'A', 1), (b'B', 2), (b'C', 3)]
+        .iter()
+        .copied()
+        .collect::<StorageHashMap<u8, i32>>();
+    let mut visited = Vec::new();
+    for (key, value) in &hmap {
+        visited.push((key, value));
+    }
+    assert_eq!(visited, hmap.iter().collect::<Vec<_>>());
+    for (_, value) in &mut hmap {
+        *value *= 10;
+    }
+    assert_eq!(hmap.values().copied().collect::<Vec<_>>(), vec![10, 20, 30]);
+}
+
 #[test]
 fn defrag_works() {
     let expected = [(b'A', 1), (b'D', 4)]
@@ -296,6 +702,38 @@ fn defrag_works() {
     assert_eq!(hmap, expected);
 }
 
+#[test]
+fn defrag_after_heavy_churn_works() {
+    let mut hmap = (0..200u8)
+        .map(|key| (key, i32::from(key) * 2))
+        .collect::<StorageHashMap<u8, i32>>();
+    // Remove most keys and re-insert some of them in between so that the
+    // vacant entries are scattered across the stash of keys.
+    for key in 0..200 {
+        if key % 5 != 0 {
+            assert_eq!(hmap.take(&key), Some(i32::from(key) * 2));
+        }
+        if key % 10 == 9 {
+            assert_eq!(hmap.insert(key, 0), None);
+        }
+    }
+    let len = hmap.len();
+    assert_eq!(len, 60);
+    assert_eq!(hmap.defrag(None), 200 - len);
+    assert_eq!(hmap.keys.capacity(), len);
+    assert_eq!(hmap.defrag(None), 0);
+    // All surviving keys still resolve to their values:
+    for key in 0..200 {
+        let expected = match key {
+            key if key % 5 == 0 => Some(i32::from(key) * 2),
+            key if key % 10 == 9 => Some(0),
+            _ => None,
+        };
+        assert_eq!(hmap.get(&key).copied(), expected);
+    }
+    assert_eq!(hmap.iter().count(), len as usize);
+}
+
 #[test]
 fn spread_layout_push_pull_works() -> ink_env::Result<()> {
     ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
@@ -312,6 +750,87 @@ fn spread_layout_push_pull_works() -> ink_env::Result<()> {
     })
 }
 
+#[test]
+fn spread_layout_works_within_contract_storage() -> ink_env::Result<()> {
+    use crate::{
+        collections::Stash as StorageStash,
+        Lazy,
+    };
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        // A contract storage layout holding a hash map alongside a value,
+        // a stash and another lazily loaded hash map.
+        type Storage = (
+            Lazy<u32>,
+            StorageStash<u8>,
+            StorageHashMap<u8, i32>,
+            Lazy<StorageHashMap<u8, i32>>,
+        );
+        let hmap = [(b'A', 1), (b'B', 2)]
+            .iter()
+            .copied()
+            .collect::<StorageHashMap<u8, i32>>();
+        let stash = [b'X', b'Y'].iter().copied().collect::<StorageStash<u8>>();
+        let storage1: Storage =
+            (Lazy::new(42), stash, hmap, Lazy::new(Default::default()));
+        SpreadLayout::push_spread(&storage1, &mut key_ptr());
+        let mut storage2 = <Storage as SpreadLayout>::pull_spread(&mut key_ptr());
+        assert_eq!(*storage2.0, 42);
+        assert_eq!(storage2.2.get(&b'A'), Some(&1));
+        assert!(storage2.3.is_empty());
+        // Mutate the hash maps and check that they do not interfere.
+        assert_eq!(storage2.2.insert(b'C', 3), None);
+        assert_eq!(storage2.3.insert(b'A', 10), None);
+        SpreadLayout::push_spread(&storage2, &mut key_ptr());
+        let storage3 = <Storage as SpreadLayout>::pull_spread(&mut key_ptr());
+        assert_eq!(*storage3.0, 42);
+        assert_eq!(storage3.1.iter().copied().collect::<Vec<_>>(), vec![b'X', b'Y']);
+        assert_eq!(storage3.2.len(), 3);
+        assert_eq!(storage3.2.get(&b'A'), Some(&1));
+        assert_eq!(storage3.2.get(&b'C'), Some(&3));
+        assert_eq!(storage3.3.len(), 1);
+        assert_eq!(storage3.3.get(&b'A'), Some(&10));
+        // We have to forget `storage2` because the `Drop` implementation of its
+        // untouched stash would otherwise try to load its lazy header from the
+        // storage region that `storage3` already cleared upon its own drop.
+        core::mem::forget(storage2);
+        Ok(())
+    })
+}
+
+#[test]
+fn spread_layout_works_with_other_hashers() -> ink_env::Result<()> {
+    use ink_env::hash::{
+        Keccak256,
+        Sha2x256,
+    };
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let test_values = [(b'A', 1), (b'B', 2), (b'C', 3)];
+        let hmap1 = test_values
+            .iter()
+            .copied()
+            .collect::<StorageHashMap<u8, i32, Keccak256>>();
+        SpreadLayout::push_spread(&hmap1, &mut key_ptr());
+        let hmap2 = <StorageHashMap<u8, i32, Keccak256> as SpreadLayout>::pull_spread(
+            &mut key_ptr(),
+        );
+        assert_eq!(hmap1, hmap2);
+        for (key, value) in &test_values {
+            assert_eq!(hmap2.get(key), Some(value));
+        }
+        // The hasher determines where the values are stored so a hash map
+        // using another hasher on the same storage region finds its keys
+        // but none of the values.
+        let hmap3 = <StorageHashMap<u8, i32, Sha2x256> as SpreadLayout>::pull_spread(
+            &mut key_ptr(),
+        );
+        assert_eq!(hmap3.len(), 3);
+        for (key, _) in &test_values {
+            assert_eq!(hmap3.get(key), None);
+        }
+        Ok(())
+    })
+}
+
 #[test]
 #[should_panic(expected = "storage entry was empty")]
 fn spread_layout_clear_works() {
@@ -329,10 +848,67 @@ fn spread_layout_clear_works() {
         // loading another instance from this storage will panic since the
         // vector's length property cannot read a value:
         SpreadLayout::clear_spread(&hmap1, &mut KeyPtr::from(root_key));
-        let _ = <StorageHashMap<u8, i32> as SpreadLayout>::pull_spread(
+        // We have to prevent calling `Drop` of `hmap2` since it has been created
+        // deliberately upon invalid contract storage. Since interacting with `hmap2`
+        // panics which immediately initiates the dropping routines we have to
+        // wrap it in `ManuallyDrop` before we interact with it to avoid to panic
+        // while panicking.
+        let hmap2 = <StorageHashMap<u8, i32> as SpreadLayout>::pull_spread(
             &mut KeyPtr::from(root_key),
         );
+        let hmap2 = core::mem::ManuallyDrop::new(hmap2);
+        // Now interact with `hmap2` to make it load from the invalid storage:
+        let _ = hmap2.len();
         Ok(())
     })
     .unwrap()
 }
+
+#[test]
+fn push_spread_writes_only_changed_cells() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let contract = ink_env::test::get_current_contract_account_id::<
+            ink_env::DefaultEnvironment,
+        >()?;
+        let storage_rw = || {
+            ink_env::test::get_contract_storage_rw::<ink_env::DefaultEnvironment>(
+                &contract,
+            )
+        };
+        let count_writes = || storage_rw().map(|(_reads, writes)| writes);
+        let hmap1 = [(b'A', 1), (b'B', 2), (b'C', 3), (b'D', 4)]
+            .iter()
+            .copied()
+            .collect::<StorageHashMap<u8, i32>>();
+        push_hmap(&hmap1);
+        let rw = storage_rw()?;
+        // Pulling and pushing an untouched instance neither reads nor writes.
+        let hmap2 = pull_hmap();
+        push_hmap(&hmap2);
+        assert_eq!(storage_rw()?, rw);
+        // Each changed value is written exactly once.
+        let mut hmap3 = pull_hmap();
+        *hmap3.get_mut(&b'B').unwrap() = 20;
+        *hmap3.get_mut(&b'D').unwrap() = 40;
+        let writes = count_writes()?;
+        push_hmap(&hmap3);
+        assert_eq!(count_writes()? - writes, 2);
+        // Writes from several mutations of the same value are not reordered.
+        let mut hmap4 = pull_hmap();
+        *hmap4.get_mut(&b'A').unwrap() = 10;
+        *hmap4.get_mut(&b'A').unwrap() = 100;
+        push_hmap(&hmap4);
+        let hmap5 = pull_hmap();
+        assert_eq!(
+            hmap5.iter().map(|(k, v)| (*k, *v)).collect::<Vec<_>>(),
+            vec![(b'A', 100), (b'B', 20), (b'C', 3), (b'D', 40)]
+        );
+        // We have to forget the other pulled instances because their `Drop`
+        // implementations would otherwise try to load their lazy headers from
+        // the storage region that `hmap5` already cleared upon its own drop.
+        core::mem::forget(hmap2);
+        core::mem::forget(hmap3);
+        core::mem::forget(hmap4);
+        Ok(())
+    })
+}
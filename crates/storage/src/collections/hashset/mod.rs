@@ -0,0 +1,159 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A storage hash set that allows to store unique values.
+
+mod impls;
+mod storage;
+
+#[cfg(test)]
+mod tests;
+
+pub use crate::collections::hashmap::Keys as Iter;
+use crate::{
+    collections::HashMap as StorageHashMap,
+    traits::PackedLayout,
+};
+use core::borrow::Borrow;
+use ink_env::hash::{
+    Blake2x256,
+    CryptoHash,
+    HashOutput,
+};
+use ink_prelude::borrow::ToOwned;
+use ink_primitives::Key;
+
+/// A hash set operating on the contract storage.
+///
+/// Stores a set of unique values.
+///
+/// # Note
+///
+/// This is a thin wrapper around a storage hash map with `()` values.
+/// Therefore it hashes its values using their [`scale::Encode`] encoding
+/// just like the storage hash map does with its keys.
+#[derive(Debug)]
+pub struct HashSet<T, H = Blake2x256>
+where
+    T: Ord + Clone + PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    /// The values of the storage hash set as keys of a storage hash map.
+    map: StorageHashMap<T, (), H>,
+}
+
+impl<T, H> HashSet<T, H>
+where
+    T: Ord + Clone + PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    /// Creates a new empty storage hash set.
+    pub fn new() -> Self {
+        Self {
+            map: StorageHashMap::new(),
+        }
+    }
+
+    /// Returns the number of values stored in the hash set.
+    pub fn len(&self) -> u32 {
+        self.map.len()
+    }
+
+    /// Returns `true` if the hash set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+
+    /// Returns an iterator yielding shared references to all values of the hash set.
+    ///
+    /// # Note
+    ///
+    /// - Avoid unbounded iteration over big storage hash sets.
+    /// - Prefer using methods like `Iterator::take` in order to limit the number
+    ///   of yielded elements.
+    pub fn iter(&self) -> Iter<T> {
+        self.map.keys()
+    }
+}
+
+impl<T, H> HashSet<T, H>
+where
+    T: Ord + Eq + Clone + PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    /// Adds the value to the set.
+    ///
+    /// Returns `true` if the set did not have this value present
+    /// and `false` otherwise.
+    ///
+    /// # Panics
+    ///
+    /// If the value is not yet present and the set already stores `u32::MAX` values.
+    pub fn insert(&mut self, value: T) -> bool {
+        if self.map.contains_key(&value) {
+            return false
+        }
+        self.map.insert(value, ());
+        true
+    }
+
+    /// Returns `true` if the set contains the value.
+    ///
+    /// The value may be any borrowed form of the set's value type,
+    /// but `Ord` and `Eq` on the borrowed form must match those for the value type.
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + PartialEq<T> + Eq + scale::Encode + ToOwned<Owned = T>,
+    {
+        self.map.contains_key(value)
+    }
+
+    /// Removes the value from the set.
+    ///
+    /// Returns `true` if the value was present in the set and `false` otherwise.
+    ///
+    /// The value may be any borrowed form of the set's value type,
+    /// but `Ord` and `Eq` on the borrowed form must match those for the value type.
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = T>,
+    {
+        self.map.take(value).is_some()
+    }
+
+    /// Removes all values from the hash set.
+    ///
+    /// # Note
+    ///
+    /// The cost of this operation is linear in the capacity of the set.
+    pub fn clear(&mut self) {
+        self.map.clear()
+    }
+
+    /// Defragments storage used by the storage hash set.
+    ///
+    /// Returns the number of storage cells freed this way.
+    ///
+    /// # Note
+    ///
+    /// See [`HashMap::defrag`](`crate::collections::HashMap::defrag`) for details.
+    pub fn defrag(&mut self, max_iterations: Option<u32>) -> u32 {
+        self.map.defrag(max_iterations)
+    }
+}
@@ -0,0 +1,74 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of ink! storage traits.
+
+use super::HashSet as StorageHashSet;
+use crate::{
+    collections::HashMap as StorageHashMap,
+    traits::{
+        KeyPtr,
+        PackedLayout,
+        SpreadLayout,
+    },
+};
+use ink_env::hash::{
+    CryptoHash,
+    HashOutput,
+};
+use ink_primitives::Key;
+
+#[cfg(feature = "std")]
+const _: () = {
+    use crate::traits::{
+        LayoutCryptoHasher,
+        StorageLayout,
+    };
+    use ink_metadata::layout::Layout;
+    use scale_info::TypeInfo;
+
+    impl<T, H> StorageLayout for StorageHashSet<T, H>
+    where
+        T: TypeInfo + Ord + Clone + PackedLayout + 'static,
+        H: LayoutCryptoHasher + CryptoHash,
+        Key: From<<H as HashOutput>::Type>,
+    {
+        fn layout(key_ptr: &mut KeyPtr) -> Layout {
+            <StorageHashMap<T, (), H> as StorageLayout>::layout(key_ptr)
+        }
+    }
+};
+
+impl<T, H> SpreadLayout for StorageHashSet<T, H>
+where
+    T: Ord + Clone + PackedLayout,
+    H: CryptoHash,
+    Key: From<<H as HashOutput>::Type>,
+{
+    const FOOTPRINT: u64 = <StorageHashMap<T, (), H> as SpreadLayout>::FOOTPRINT;
+
+    fn pull_spread(ptr: &mut KeyPtr) -> Self {
+        Self {
+            map: SpreadLayout::pull_spread(ptr),
+        }
+    }
+
+    fn push_spread(&self, ptr: &mut KeyPtr) {
+        SpreadLayout::push_spread(&self.map, ptr);
+    }
+
+    fn clear_spread(&self, ptr: &mut KeyPtr) {
+        SpreadLayout::clear_spread(&self.map, ptr);
+    }
+}
@@ -0,0 +1,136 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::HashSet as StorageHashSet;
+use crate::traits::{
+    KeyPtr,
+    SpreadLayout,
+};
+use ink_primitives::Key;
+
+/// Returns always the same `KeyPtr`.
+fn key_ptr() -> KeyPtr {
+    let root_key = Key::from([0x42; 32]);
+    KeyPtr::from(root_key)
+}
+
+#[test]
+fn new_works() {
+    // `StorageHashSet::new`
+    let set = <StorageHashSet<u8>>::new();
+    assert!(set.is_empty());
+    assert_eq!(set.len(), 0);
+    assert!(set.iter().next().is_none());
+    // `StorageHashSet::default`
+    let default = <StorageHashSet<u8> as Default>::default();
+    assert!(default.is_empty());
+    assert_eq!(default.len(), 0);
+    assert!(default.iter().next().is_none());
+    // `StorageHashSet::new` and `StorageHashSet::default` should be equal.
+    assert_eq!(set, default);
+}
+
+#[test]
+fn insert_works() {
+    let mut set = <StorageHashSet<u8>>::new();
+    assert!(!set.contains(&b'A'));
+    assert!(set.insert(b'A'));
+    assert!(set.contains(&b'A'));
+    assert_eq!(set.len(), 1);
+    // Inserting the same value again does not change the set.
+    assert!(!set.insert(b'A'));
+    assert!(set.contains(&b'A'));
+    assert_eq!(set.len(), 1);
+    assert!(set.insert(b'B'));
+    assert_eq!(set.len(), 2);
+}
+
+#[test]
+fn remove_works() {
+    let mut set = [b'A', b'B', b'C'].iter().copied().collect::<StorageHashSet<u8>>();
+    assert_eq!(set.len(), 3);
+    assert!(set.remove(&b'B'));
+    assert!(!set.contains(&b'B'));
+    assert_eq!(set.len(), 2);
+    // Removing a value that is not in the set has no effect.
+    assert!(!set.remove(&b'B'));
+    assert!(!set.remove(&b'D'));
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(&b'A'));
+    assert!(set.contains(&b'C'));
+}
+
+#[test]
+fn from_iterator_deduplicates() {
+    let set = [b'A', b'B', b'A', b'C', b'B']
+        .iter()
+        .copied()
+        .collect::<StorageHashSet<u8>>();
+    assert_eq!(set.len(), 3);
+    let mut values = set.iter().copied().collect::<Vec<_>>();
+    values.sort_unstable();
+    assert_eq!(values, vec![b'A', b'B', b'C']);
+}
+
+#[test]
+fn clear_works() {
+    let mut set = [b'A', b'B', b'C'].iter().copied().collect::<StorageHashSet<u8>>();
+    set.clear();
+    assert!(set.is_empty());
+    assert!(!set.contains(&b'A'));
+    assert!(set.insert(b'A'));
+    assert_eq!(set.len(), 1);
+}
+
+#[test]
+fn spread_layout_push_pull_works() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let set1 = [b'A', b'B', b'C'].iter().copied().collect::<StorageHashSet<u8>>();
+        SpreadLayout::push_spread(&set1, &mut key_ptr());
+        // Load the pushed storage hash set into another instance and check that
+        // both instances are equal:
+        let set2 = <StorageHashSet<u8> as SpreadLayout>::pull_spread(&mut key_ptr());
+        assert_eq!(set1, set2);
+        assert!(set2.contains(&b'B'));
+        assert!(!set2.contains(&b'D'));
+        Ok(())
+    })
+}
+
+#[test]
+#[should_panic(expected = "storage entry was empty")]
+fn spread_layout_clear_works() {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let set1 = [b'A', b'B', b'C'].iter().copied().collect::<StorageHashSet<u8>>();
+        SpreadLayout::push_spread(&set1, &mut key_ptr());
+        // It has already been asserted that a valid instance can be pulled
+        // from contract storage after a push to the same storage region.
+        //
+        // Now clear the associated storage from `set1` and check whether
+        // loading another instance from this storage will panic since the
+        // set's length property cannot read a value:
+        SpreadLayout::clear_spread(&set1, &mut key_ptr());
+        // We have to prevent calling `Drop` of `set2` since it has been created
+        // deliberately upon invalid contract storage. Since interacting with `set2`
+        // panics which immediately initiates the dropping routines we have to
+        // wrap it in `ManuallyDrop` before we interact with it to avoid to panic
+        // while panicking.
+        let set2 = <StorageHashSet<u8> as SpreadLayout>::pull_spread(&mut key_ptr());
+        let set2 = core::mem::ManuallyDrop::new(set2);
+        // Now interact with `set2` to make it load from the invalid storage:
+        let _ = set2.len();
+        Ok(())
+    })
+    .unwrap()
+}
@@ -21,7 +21,10 @@
 pub mod binary_heap;
 pub mod bitstash;
 pub mod bitvec;
+pub mod btreemap;
 pub mod hashmap;
+pub mod hashset;
+pub mod queue;
 pub mod smallvec;
 pub mod stash;
 pub mod vec;
@@ -31,7 +34,10 @@ pub use self::{
     binary_heap::BinaryHeap,
     bitstash::BitStash,
     bitvec::Bitvec,
+    btreemap::BTreeMap,
     hashmap::HashMap,
+    hashset::HashSet,
+    queue::Queue,
     smallvec::SmallVec,
     stash::Stash,
     vec::Vec,
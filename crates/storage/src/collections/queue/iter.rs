@@ -0,0 +1,97 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use super::Queue as StorageQueue;
+use crate::traits::PackedLayout;
+
+/// An iterator over shared references to the elements of a storage queue.
+///
+/// Yields the elements from the front to the back of the queue.
+#[derive(Debug, Clone, Copy)]
+pub struct Iter<'a, T>
+where
+    T: PackedLayout,
+{
+    /// The storage queue to iterate over.
+    queue: &'a StorageQueue<T>,
+    /// The current begin of the iteration as offset from the front.
+    begin: u32,
+    /// The current end of the iteration as offset from the front.
+    end: u32,
+}
+
+impl<'a, T> Iter<'a, T>
+where
+    T: PackedLayout,
+{
+    /// Creates a new iterator for the given storage queue.
+    pub(crate) fn new(queue: &'a StorageQueue<T>) -> Self {
+        Self {
+            queue,
+            begin: 0,
+            end: queue.len(),
+        }
+    }
+
+    /// Returns the amount of remaining elements to yield by the iterator.
+    fn remaining(&self) -> u32 {
+        self.end - self.begin
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T>
+where
+    T: PackedLayout,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        debug_assert!(self.begin <= self.end);
+        if self.begin == self.end {
+            return None
+        }
+        let cur = self.begin;
+        self.begin += 1;
+        self.queue.get(cur).expect("access is within bounds").into()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining() as usize;
+        (remaining, Some(remaining))
+    }
+
+    fn count(self) -> usize {
+        self.remaining() as usize
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Iter<'a, T> where T: PackedLayout {}
+
+impl<'a, T> DoubleEndedIterator for Iter<'a, T>
+where
+    T: PackedLayout,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        debug_assert!(self.begin <= self.end);
+        if self.begin == self.end {
+            return None
+        }
+        self.end -= 1;
+        self.queue
+            .get(self.end)
+            .expect("access is within bounds")
+            .into()
+    }
+}
@@ -0,0 +1,207 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A storage queue used to store elements in first-in first-out order.
+//!
+//! Useful for work queues and streaming patterns where elements are pushed
+//! at the back and popped from the front.
+
+mod impls;
+mod iter;
+mod storage;
+
+#[cfg(test)]
+mod tests;
+
+pub use self::iter::Iter;
+use crate::{
+    lazy::{
+        Lazy,
+        LazyIndexMap,
+    },
+    traits::PackedLayout,
+};
+
+/// A first-in first-out queue implemented as a growable ring buffer.
+///
+/// # Note
+///
+/// Elements are never shifted around when popping from the front of the
+/// queue. Instead the storage cells of popped elements are reused by elements
+/// that are pushed afterwards.
+///
+/// Whenever the ring buffer is full its capacity is doubled. This relocates
+/// only the elements that have wrapped around to the front of the ring buffer
+/// which amortizes to a constant number of moved elements per push.
+///
+/// Allows to store up to `2^31` elements.
+#[derive(Debug)]
+pub struct Queue<T>
+where
+    T: PackedLayout,
+{
+    /// The header holding the cursors of the queue.
+    header: Lazy<Header>,
+    /// The synchronized cells to operate on the contract storage.
+    elems: LazyIndexMap<T>,
+}
+
+/// Stores the cursors of the ring buffer of the storage queue.
+#[derive(Debug, Copy, Clone, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+struct Header {
+    /// The index of the cell holding the front element.
+    head: u32,
+    /// The number of elements stored in the queue.
+    len: u32,
+    /// The number of cells of the ring buffer.
+    capacity: u32,
+}
+
+impl<T> Queue<T>
+where
+    T: PackedLayout,
+{
+    /// Creates a new empty storage queue.
+    pub fn new() -> Self {
+        Self {
+            header: Lazy::new(Header {
+                head: 0,
+                len: 0,
+                capacity: 0,
+            }),
+            elems: LazyIndexMap::new(),
+        }
+    }
+
+    /// Returns the number of elements in the queue.
+    pub fn len(&self) -> u32 {
+        self.header.len
+    }
+
+    /// Returns `true` if the queue contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the index of the cell holding the element at the given offset
+    /// from the front of the queue.
+    fn cell_index(&self, offset: u32) -> u32 {
+        let Header { head, capacity, .. } = *self.header;
+        debug_assert!(offset < capacity);
+        (head + offset) % capacity
+    }
+
+    /// Returns a shared reference to the element at the given offset from the
+    /// front of the queue.
+    ///
+    /// Returns `None` if `offset` is out of bounds.
+    fn get(&self, offset: u32) -> Option<&T> {
+        if offset >= self.len() {
+            return None
+        }
+        self.elems.get(self.cell_index(offset))
+    }
+
+    /// Returns an iterator yielding shared references to all elements of the
+    /// queue from front to back.
+    ///
+    /// # Note
+    ///
+    /// Avoid unbounded iteration over big storage queues.
+    /// Prefer using methods like `Iterator::take` in order to limit the number
+    /// of yielded elements.
+    pub fn iter(&self) -> Iter<T> {
+        Iter::new(self)
+    }
+
+    /// Returns a shared reference to the front element if any.
+    pub fn front(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns an exclusive reference to the front element if any.
+    pub fn front_mut(&mut self) -> Option<&mut T> {
+        if self.is_empty() {
+            return None
+        }
+        let index = self.cell_index(0);
+        self.elems.get_mut(index)
+    }
+
+    /// Appends an element to the back of the queue.
+    ///
+    /// # Panics
+    ///
+    /// If the queue already holds `2^31` elements.
+    pub fn push_back(&mut self, value: T) {
+        if self.len() == self.header.capacity {
+            self.grow();
+        }
+        let index = self.cell_index(self.len());
+        self.header.len += 1;
+        self.elems.put(index, Some(value));
+    }
+
+    /// Pops the front element from the queue and returns it.
+    ///
+    /// Returns `None` if the queue is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            return None
+        }
+        let index = self.cell_index(0);
+        let header = &mut *self.header;
+        header.head = (index + 1) % header.capacity;
+        header.len -= 1;
+        self.elems.put_get(index, None)
+    }
+
+    /// Doubles the capacity of the full ring buffer.
+    ///
+    /// The elements that have wrapped around to the front of the ring buffer
+    /// are moved behind its old end so that all elements stay in order.
+    fn grow(&mut self) {
+        let Header { head, capacity, .. } = *self.header;
+        assert!(
+            capacity <= 1 << 30,
+            "cannot push more elements into the storage queue"
+        );
+        for index in 0..head {
+            let value = self.elems.put_get(index, None);
+            self.elems.put(capacity + index, value);
+        }
+        self.header.capacity = core::cmp::max(capacity * 2, 1);
+    }
+
+    /// Clears the underlying storage cells of the storage queue.
+    ///
+    /// # Note
+    ///
+    /// This completely invalidates the storage queue's invariances about
+    /// the contents of its associated storage region.
+    ///
+    /// This API is used for the `Drop` implementation of [`Queue`] as well as
+    /// for the [`SpreadLayout::clear_spread`] trait implementation.
+    fn clear_cells(&self) {
+        if self.elems.key().is_none() {
+            // We won't clear any storage if we are in lazy state since there
+            // probably has not been any state written to storage, yet.
+            return
+        }
+        for offset in 0..self.len() {
+            self.elems.clear_packed_at(self.cell_index(offset));
+        }
+    }
+}
@@ -0,0 +1,146 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+
+use super::Queue as StorageQueue;
+use crate::traits::{
+    KeyPtr,
+    SpreadLayout,
+};
+use ink_primitives::Key;
+
+/// Returns always the same `KeyPtr`.
+fn key_ptr() -> KeyPtr {
+    let root_key = Key::from([0x42; 32]);
+    KeyPtr::from(root_key)
+}
+
+/// Asserts that the queue yields exactly the expected elements from front to back.
+fn assert_elements(queue: &StorageQueue<u32>, expected: &[u32]) {
+    assert_eq!(queue.len(), expected.len() as u32);
+    assert_eq!(queue.iter().len(), expected.len());
+    assert_eq!(queue.iter().copied().collect::<Vec<_>>(), expected);
+    let mut reversed = expected.to_vec();
+    reversed.reverse();
+    assert_eq!(queue.iter().rev().copied().collect::<Vec<_>>(), reversed);
+}
+
+#[test]
+fn new_works() {
+    let queue = <StorageQueue<u32>>::new();
+    assert!(queue.is_empty());
+    assert_eq!(queue.len(), 0);
+    assert_eq!(queue.front(), None);
+    assert!(queue.iter().next().is_none());
+    assert_eq!(queue, <StorageQueue<u32> as Default>::default());
+}
+
+#[test]
+fn push_back_and_pop_front_works() {
+    let mut queue = <StorageQueue<u32>>::new();
+    for value in 0..10 {
+        queue.push_back(value);
+        assert_eq!(queue.front(), Some(&0));
+    }
+    assert_elements(&queue, &(0..10).collect::<Vec<_>>());
+    for value in 0..10 {
+        assert_eq!(queue.pop_front(), Some(value));
+        assert_eq!(queue.len(), 9 - value);
+    }
+    assert_eq!(queue.pop_front(), None);
+    assert!(queue.is_empty());
+}
+
+#[test]
+fn front_mut_works() {
+    let mut queue = [1, 2, 3].iter().copied().collect::<StorageQueue<u32>>();
+    *queue.front_mut().unwrap() = 42;
+    assert_elements(&queue, &[42, 2, 3]);
+    assert_eq!(<StorageQueue<u32>>::new().front_mut(), None);
+}
+
+#[test]
+fn pop_front_reuses_cells_after_wrap_around() {
+    let mut queue = <StorageQueue<u32>>::new();
+    queue.extend(0..3);
+    let capacity = queue.header.capacity;
+    // Keep the length of the queue bounded while the cursors wrap around
+    // the ring buffer many times over.
+    for value in 3..1000 {
+        queue.push_back(value);
+        assert_eq!(queue.pop_front(), Some(value - 3));
+        assert_eq!(queue.front(), Some(&(value - 2)));
+    }
+    assert_eq!(queue.header.capacity, capacity);
+    assert_elements(&queue, &[997, 998, 999]);
+}
+
+#[test]
+fn push_back_grows_wrapped_ring_buffer() {
+    let mut queue = <StorageQueue<u32>>::new();
+    let mut expected = std::collections::VecDeque::new();
+    // Alternate between pushing and popping so that the ring buffer is full
+    // with its elements wrapped around whenever it has to grow.
+    for value in 0..500 {
+        queue.push_back(value);
+        expected.push_back(value);
+        if value % 3 == 0 {
+            assert_eq!(queue.pop_front(), expected.pop_front());
+        }
+        assert_eq!(queue.front(), expected.front());
+    }
+    assert_elements(&queue, &expected.iter().copied().collect::<Vec<_>>());
+    assert!(queue.header.capacity < 2 * queue.len());
+}
+
+#[test]
+fn spread_layout_push_pull_works() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let mut queue1 = (0..10).collect::<StorageQueue<u32>>();
+        for value in 10..25 {
+            queue1.push_back(value);
+            queue1.pop_front();
+        }
+        SpreadLayout::push_spread(&queue1, &mut key_ptr());
+        // Load the pushed storage queue into another instance and check that
+        // both instances are equal:
+        let mut queue2 =
+            <StorageQueue<u32> as SpreadLayout>::pull_spread(&mut key_ptr());
+        assert_eq!(queue1, queue2);
+        // The pulled instance continues wrapping around where the other one
+        // stopped:
+        for value in 25..40 {
+            queue2.push_back(value);
+            assert_eq!(queue2.pop_front(), Some(value - 10));
+        }
+        assert_elements(&queue2, &(30..40).collect::<Vec<_>>());
+        Ok(())
+    })
+}
+
+#[test]
+#[should_panic(expected = "encountered empty storage cell")]
+fn spread_layout_clear_works() {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let queue1 = (0..4).collect::<StorageQueue<u32>>();
+        SpreadLayout::push_spread(&queue1, &mut key_ptr());
+        // Now clear the associated storage from `queue1` and check whether
+        // loading another instance from this storage will panic since the
+        // queue's header cannot read a value:
+        SpreadLayout::clear_spread(&queue1, &mut key_ptr());
+        let _ = <StorageQueue<u32> as SpreadLayout>::pull_spread(&mut key_ptr());
+        Ok(())
+    })
+    .unwrap()
+}
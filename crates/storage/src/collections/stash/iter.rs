@@ -20,6 +20,7 @@ use crate::{
     collections::extend_lifetime,
     traits::PackedLayout,
 };
+use core::iter::FusedIterator;
 
 /// An iterator over shared references to the elements of a storage stash.
 #[derive(Debug, Clone, Copy)]
@@ -39,6 +40,14 @@ where
     begin: u32,
     /// The current end of the iteration.
     end: u32,
+    /// The number of entries loaded at once when iterating from the front.
+    ///
+    /// # Note
+    ///
+    /// Prefetching is disabled if this is zero.
+    batch: u32,
+    /// The end of the entries that have already been prefetched.
+    prefetched: u32,
 }
 
 impl<'a, T> Iter<'a, T>
@@ -47,44 +56,98 @@ where
 {
     /// Creates a new iterator for the given storage stash.
     pub(crate) fn new(stash: &'a Stash<T>) -> Self {
+        Self::prefetched(stash, 0)
+    }
+
+    /// Creates a new iterator for the given storage stash that loads `batch`
+    /// entries at once when iterating from the front.
+    pub(crate) fn prefetched(stash: &'a Stash<T>, batch: u32) -> Self {
         Self {
             stash,
             yielded: 0,
             begin: 0,
             end: stash.len_entries(),
+            batch,
+            prefetched: 0,
         }
     }
 
+    /// Loads the batch of entries starting at `index` into the cache unless
+    /// `index` has already been prefetched.
+    fn prefetch(&mut self, index: u32) {
+        if self.batch == 0 || index < self.prefetched {
+            return
+        }
+        debug_assert!(index < self.end);
+        let count = core::cmp::min(self.batch, self.end - index);
+        let _ = self.stash.entries.load_range(index, count);
+        self.prefetched = index + count;
+    }
+
     /// Returns the amount of remaining elements to yield by the iterator.
     fn remaining(&self) -> u32 {
         self.stash.len() - self.yielded
     }
 }
 
-impl<'a, T> Iterator for Iter<'a, T>
+impl<'a, T> Iter<'a, T>
 where
     T: PackedLayout,
 {
-    type Item = &'a T;
-
-    fn next(&mut self) -> Option<Self::Item> {
+    /// Returns the next element from the front together with its index.
+    fn next_with_index(&mut self) -> Option<(u32, &'a T)> {
         loop {
             debug_assert!(self.begin <= self.end);
-            if self.begin == self.end {
+            if self.begin == self.end || self.remaining() == 0 {
+                // Bail out early if all occupied entries have been yielded
+                // so that we do not load the remaining vacant entries.
                 return None
             }
             let cur = self.begin;
             self.begin += 1;
+            self.prefetch(cur);
             match self.stash.get(cur) {
                 Some(value) => {
                     self.yielded += 1;
-                    return Some(value)
+                    return Some((cur, value))
                 }
                 None => continue,
             }
         }
     }
 
+    /// Returns the next element from the back together with its index.
+    fn next_back_with_index(&mut self) -> Option<(u32, &'a T)> {
+        loop {
+            debug_assert!(self.begin <= self.end);
+            if self.begin == self.end || self.remaining() == 0 {
+                // Bail out early if all occupied entries have been yielded
+                // so that we do not load the remaining vacant entries.
+                return None
+            }
+            debug_assert_ne!(self.end, 0);
+            self.end -= 1;
+            match self.stash.get(self.end) {
+                Some(value) => {
+                    self.yielded += 1;
+                    return Some((self.end, value))
+                }
+                None => continue,
+            }
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T>
+where
+    T: PackedLayout,
+{
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_with_index().map(|(_, value)| value)
+    }
+
     fn size_hint(&self) -> (usize, Option<usize>) {
         let remaining = self.remaining() as usize;
         (remaining, Some(remaining))
@@ -97,29 +160,71 @@ where
 
 impl<'a, T> ExactSizeIterator for Iter<'a, T> where T: PackedLayout {}
 
+impl<'a, T> FusedIterator for Iter<'a, T> where T: PackedLayout {}
+
 impl<'a, T> DoubleEndedIterator for Iter<'a, T>
 where
     T: PackedLayout,
 {
     fn next_back(&mut self) -> Option<Self::Item> {
-        loop {
-            debug_assert!(self.begin <= self.end);
-            if self.begin == self.end {
-                return None
-            }
-            debug_assert_ne!(self.end, 0);
-            self.end -= 1;
-            match self.stash.get(self.end) {
-                Some(value) => {
-                    self.yielded += 1;
-                    return Some(value)
-                }
-                None => continue,
-            }
+        self.next_back_with_index().map(|(_, value)| value)
+    }
+}
+
+/// An iterator over the indices of the occupied entries of a storage stash.
+#[derive(Debug, Clone, Copy)]
+pub struct Keys<'a, T>
+where
+    T: PackedLayout,
+{
+    /// The iterator over the elements whose indices are yielded.
+    iter: Iter<'a, T>,
+}
+
+impl<'a, T> Keys<'a, T>
+where
+    T: PackedLayout,
+{
+    /// Creates a new iterator for the given storage stash.
+    pub(crate) fn new(stash: &'a Stash<T>) -> Self {
+        Self {
+            iter: Iter::new(stash),
         }
     }
 }
 
+impl<'a, T> Iterator for Keys<'a, T>
+where
+    T: PackedLayout,
+{
+    type Item = u32;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next_with_index().map(|(index, _)| index)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+
+    fn count(self) -> usize {
+        self.iter.count()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Keys<'a, T> where T: PackedLayout {}
+
+impl<'a, T> FusedIterator for Keys<'a, T> where T: PackedLayout {}
+
+impl<'a, T> DoubleEndedIterator for Keys<'a, T>
+where
+    T: PackedLayout,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back_with_index().map(|(index, _)| index)
+    }
+}
+
 /// An iterator over exclusive references to the elements of a storage stash.
 #[derive(Debug)]
 pub struct IterMut<'a, T>
@@ -188,7 +293,9 @@ where
     fn next(&mut self) -> Option<Self::Item> {
         loop {
             debug_assert!(self.begin <= self.end);
-            if self.begin == self.end {
+            if self.begin == self.end || self.remaining() == 0 {
+                // Bail out early if all occupied entries have been yielded
+                // so that we do not load the remaining vacant entries.
                 return None
             }
             let cur = self.begin;
@@ -215,6 +322,8 @@ where
 
 impl<'a, T> ExactSizeIterator for IterMut<'a, T> where T: PackedLayout {}
 
+impl<'a, T> FusedIterator for IterMut<'a, T> where T: PackedLayout {}
+
 impl<'a, T> DoubleEndedIterator for IterMut<'a, T>
 where
     T: PackedLayout,
@@ -222,7 +331,9 @@ where
     fn next_back(&mut self) -> Option<Self::Item> {
         loop {
             debug_assert!(self.begin <= self.end);
-            if self.begin == self.end {
+            if self.begin == self.end || self.remaining() == 0 {
+                // Bail out early if all occupied entries have been yielded
+                // so that we do not load the remaining vacant entries.
                 return None
             }
             debug_assert_ne!(self.end, 0);
@@ -300,6 +411,8 @@ where
 
 impl<'a, T> ExactSizeIterator for Entries<'a, T> where T: PackedLayout {}
 
+impl<'a, T> FusedIterator for Entries<'a, T> where T: PackedLayout {}
+
 impl<'a, T> DoubleEndedIterator for Entries<'a, T>
 where
     T: PackedLayout,
@@ -319,3 +432,70 @@ where
         Some(entry)
     }
 }
+
+/// A draining iterator over the elements of a storage stash.
+///
+/// Yields the elements of the stash together with their indices.
+#[derive(Debug)]
+pub struct Drain<'a, T>
+where
+    T: PackedLayout,
+{
+    /// The storage stash to drain.
+    stash: &'a mut Stash<T>,
+    /// The current begin of the iteration.
+    begin: u32,
+}
+
+impl<'a, T> Drain<'a, T>
+where
+    T: PackedLayout,
+{
+    /// Creates a new draining iterator for the given storage stash.
+    pub(crate) fn new(stash: &'a mut Stash<T>) -> Self {
+        Self { stash, begin: 0 }
+    }
+}
+
+impl<'a, T> Iterator for Drain<'a, T>
+where
+    T: PackedLayout,
+{
+    type Item = (u32, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.stash.is_empty() {
+                // Reset the stash once all elements have been taken so that
+                // it no longer holds on to the remaining vacant entries.
+                self.stash.clear();
+                return None
+            }
+            debug_assert!(self.begin < self.stash.len_entries());
+            let cur = self.begin;
+            self.begin += 1;
+            if let Some(value) = self.stash.take(cur) {
+                return Some((cur, value))
+            }
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.stash.len() as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Drain<'a, T> where T: PackedLayout {}
+
+impl<'a, T> FusedIterator for Drain<'a, T> where T: PackedLayout {}
+
+impl<'a, T> Drop for Drain<'a, T>
+where
+    T: PackedLayout,
+{
+    fn drop(&mut self) {
+        // Remove the elements that have not been yielded without loading them.
+        self.stash.clear();
+    }
+}
@@ -23,13 +23,17 @@ mod tests;
 
 use self::iter::Entries;
 pub use self::iter::{
+    Drain,
     Iter,
     IterMut,
+    Keys,
 };
 use crate::{
-    lazy::LazyIndexMap,
+    lazy::{
+        LazyCell,
+        LazyIndexMap,
+    },
     traits::PackedLayout,
-    Pack,
 };
 use ink_primitives::Key;
 
@@ -47,7 +51,12 @@ where
     T: PackedLayout,
 {
     /// The combined and commonly used header data.
-    header: Pack<Header>,
+    ///
+    /// # Note
+    ///
+    /// This is loaded lazily and only written back to the contract storage
+    /// if it has been mutated so that untouched stashes cost nothing.
+    header: LazyCell<Header>,
     /// The storage entries of the stash.
     entries: LazyIndexMap<Entry<T>>,
 }
@@ -74,6 +83,26 @@ struct Header {
     len_entries: u32,
 }
 
+/// Error returned by [`Stash::put_at`] if the entry at the index is occupied.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct OccupiedError;
+
+/// Error returned by [`Stash::try_put`] if the element cannot be put into the stash.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StashError {
+    /// The stash has no vacant entries and already stores `u32::MAX` entries.
+    Full,
+}
+
+impl Header {
+    /// The header of an empty stash.
+    const EMPTY: Self = Self {
+        last_vacant: 0,
+        len: 0,
+        len_entries: 0,
+    };
+}
+
 /// A vacant entry with previous and next vacant indices.
 #[derive(Debug, Copy, Clone, scale::Encode, scale::Decode)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
@@ -135,18 +164,42 @@ where
     /// Creates a new empty stash.
     pub fn new() -> Self {
         Self {
-            header: Pack::new(Header {
-                last_vacant: 0,
-                len: 0,
-                len_entries: 0,
-            }),
+            header: LazyCell::new(Some(Header::EMPTY)),
             entries: LazyIndexMap::new(),
         }
     }
 
+    /// Returns a shared reference to the header of the stash.
+    ///
+    /// # Note
+    ///
+    /// This loads the header from the contract storage if this did not
+    /// happen before.
+    ///
+    /// # Panics
+    ///
+    /// If the header of a pulled stash is missing from the contract storage.
+    fn header(&self) -> &Header {
+        self.header.get().expect("storage entry was empty")
+    }
+
+    /// Returns an exclusive reference to the header of the stash.
+    ///
+    /// # Note
+    ///
+    /// This marks the header as mutated so that it is written back upon the
+    /// next flush.
+    ///
+    /// # Panics
+    ///
+    /// If the header of a pulled stash is missing from the contract storage.
+    fn header_mut(&mut self) -> &mut Header {
+        self.header.get_mut().expect("storage entry was empty")
+    }
+
     /// Returns the number of elements stored in the stash.
     pub fn len(&self) -> u32 {
-        self.header.len
+        self.header().len
     }
 
     /// Returns `true` if the stash contains no elements.
@@ -166,7 +219,7 @@ where
 
     /// Returns the number of entries currently managed by the storage stash.
     fn len_entries(&self) -> u32 {
-        self.header.len_entries
+        self.header().len_entries
     }
 
     /// Returns the underlying key to the cells.
@@ -190,6 +243,24 @@ where
         Iter::new(self)
     }
 
+    /// Returns an iterator yielding shared references to all elements of the stash
+    /// that loads `batch` entries at once.
+    ///
+    /// # Note
+    ///
+    /// This yields the same elements as [`Stash::iter`] but warms the cache
+    /// with the next `batch` entries, vacant ones included, whenever iterating
+    /// from the front reaches an entry that has not been loaded that way, yet.
+    /// Iterating from the back still loads one entry at a time.
+    /// A `batch` of zero disables prefetching.
+    ///
+    /// Avoid unbounded iteration over big storage stashes.
+    /// Prefer using methods like `Iterator::take` in order to limit the number
+    /// of yielded elements.
+    pub fn iter_prefetched(&self, batch: u32) -> Iter<T> {
+        Iter::prefetched(self, batch)
+    }
+
     /// Returns an iterator yielding exclusive references to all elements of the stash.
     ///
     /// # Note
@@ -201,20 +272,65 @@ where
         IterMut::new(self)
     }
 
+    /// Returns an iterator yielding the indices of all elements of the stash.
+    ///
+    /// # Note
+    ///
+    /// Avoid unbounded iteration over big storage stashes.
+    /// Prefer using methods like `Iterator::take` in order to limit the number
+    /// of yielded indices.
+    pub fn keys(&self) -> Keys<T> {
+        Keys::new(self)
+    }
+
+    /// Returns an iterator yielding shared references to all elements of the stash.
+    ///
+    /// # Note
+    ///
+    /// This is the same as [`Stash::iter`] and is provided for symmetry
+    /// with [`Stash::keys`].
+    pub fn values(&self) -> Iter<T> {
+        self.iter()
+    }
+
     /// Returns an iterator yielding shared references to all entries of the stash.
     pub fn entries(&self) -> Entries<T> {
         Entries::new(self)
     }
 
+    /// Returns an iterator that removes all elements from the stash and yields
+    /// them together with their indices.
+    ///
+    /// # Note
+    ///
+    /// - The stash is empty afterwards and subsequent calls to [`Stash::put`]
+    ///   start again at index `0`.
+    /// - If the iterator is dropped before it is exhausted the remaining
+    ///   elements are removed without being loaded from the contract storage.
+    pub fn drain(&mut self) -> Drain<T> {
+        Drain::new(self)
+    }
+
+    /// Returns the index that the next call to [`Stash::put`] will put the element into.
+    ///
+    /// # Note
+    ///
+    /// This is the most recent vacant index or the index past the last entry
+    /// if all entries are occupied.
+    pub fn peek_next_index(&self) -> Index {
+        self.last_vacant_index()
+            .unwrap_or_else(|| self.header().len_entries)
+    }
+
     /// Returns `true` if the storage stash has vacant entries.
     fn has_vacant_entries(&self) -> bool {
-        self.header.len != self.header.len_entries
+        self.header().len != self.header().len_entries
     }
 
     /// Returns the index of the last vacant entry if any.
     fn last_vacant_index(&self) -> Option<Index> {
         if self.has_vacant_entries() {
-            Some(self.header.last_vacant)
+            Some(self.header().last_vacant)
         } else {
             None
         }
@@ -239,6 +355,27 @@ where
         })
     }
 
+    /// Returns `true` if the stash contains an element at the given index.
+    ///
+    /// # Note
+    ///
+    /// This does not load the indexed entry from the contract storage if the
+    /// index is out of bounds or if the stash has no vacant entries.
+    pub fn contains(&self, at: Index) -> bool {
+        if at >= self.len_entries() {
+            // Bail out early if the index is out of bounds.
+            return false
+        }
+        if !self.has_vacant_entries() {
+            // All entries within bounds are occupied.
+            return true
+        }
+        self.entries
+            .get(at)
+            .map(Entry::is_occupied)
+            .unwrap_or(false)
+    }
+
     /// Returns an exclusive reference to the element at the given index.
     pub fn get_mut(&mut self, at: Index) -> Option<&mut T> {
         if at >= self.len_entries() {
@@ -301,7 +438,7 @@ where
         if prev_vacant == removed_index && next_vacant == removed_index {
             // There is no other vacant entry left in the storage stash so
             // there is nothing to update. Bail out early.
-            self.header.last_vacant = self.header.len;
+            self.header_mut().last_vacant = self.header().len;
             return
         }
         if prev_vacant == next_vacant {
@@ -339,8 +476,8 @@ where
         // Bind the last vacant pointer to the vacant position with the lower index.
         // This has the effect that lower indices are refilled more quickly.
         use core::cmp::min;
-        if removed_index == self.header.last_vacant {
-            self.header.last_vacant = min(prev_vacant, next_vacant);
+        if removed_index == self.header().last_vacant {
+            self.header_mut().last_vacant = min(prev_vacant, next_vacant);
         }
     }
 
@@ -421,7 +558,27 @@ where
     /// Put the element into the stash at the next vacant position.
     ///
     /// Returns the stash index that the element was put into.
+    ///
+    /// # Panics
+    ///
+    /// If the stash has no vacant entries and already stores `u32::MAX` entries.
     pub fn put(&mut self, new_value: T) -> Index {
+        self.try_put(new_value)
+            .expect("cannot put more elements into the storage stash")
+    }
+
+    /// Put the element into the stash at the next vacant position.
+    ///
+    /// Returns the stash index that the element was put into.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`StashError::Full`] and leaves the stash unchanged if the stash
+    /// has no vacant entries and already stores `u32::MAX` entries.
+    pub fn try_put(&mut self, new_value: T) -> Result<Index, StashError> {
+        if !self.has_vacant_entries() && self.len_entries() == u32::MAX {
+            return Err(StashError::Full)
+        }
         let new_entry = Some(Entry::Occupied(new_value));
         let new_index = if let Some(index) = self.last_vacant_index() {
             // Put the new element to the most recent vacant index if not all entries are occupied.
@@ -439,14 +596,64 @@ where
             index
         } else {
             // Push the new element to the end if all entries are occupied.
-            let new_index = self.header.len_entries;
+            let new_index = self.header().len_entries;
             self.entries.put(new_index, new_entry);
-            self.header.last_vacant += 1;
-            self.header.len_entries += 1;
+            self.header_mut().last_vacant += 1;
+            self.header_mut().len_entries += 1;
             new_index
         };
-        self.header.len += 1;
-        new_index
+        self.header_mut().len += 1;
+        Ok(new_index)
+    }
+
+    /// Put the element into the stash at the given index.
+    ///
+    /// This is useful to keep the indices of two stashes in sync, e.g. when
+    /// replicating the elements of one stash into another.
+    ///
+    /// If `at` is out of bounds the stash is extended with vacant entries
+    /// up to `at`. Note that this is linear in the number of added entries.
+    ///
+    /// # Errors
+    ///
+    /// If the entry at the given index is already occupied.
+    ///
+    /// # Panics
+    ///
+    /// If `at` is out of bounds and equal to `u32::MAX`.
+    pub fn put_at(&mut self, at: Index, new_value: T) -> Result<(), OccupiedError> {
+        if at >= self.len_entries() {
+            assert!(at < u32::MAX, "cannot put more elements into the storage stash");
+            self.push_vacant_entries(at + 1);
+        }
+        let entry = self.entries.get_mut(at).expect("index is out of bounds");
+        if !entry.is_vacant() {
+            return Err(OccupiedError)
+        }
+        let vacant_entry = match core::mem::replace(entry, Entry::Occupied(new_value)) {
+            Entry::Vacant(vacant_entry) => vacant_entry,
+            Entry::Occupied(_) => {
+                unreachable!("the replaced entry is known to be vacant")
+            }
+        };
+        self.remove_vacant_entry(at, vacant_entry);
+        self.header_mut().len += 1;
+        Ok(())
+    }
+
+    /// Pushes vacant entries to the end until the stash has `new_len_entries` entries.
+    fn push_vacant_entries(&mut self, new_len_entries: u32) {
+        for at in self.len_entries()..new_len_entries {
+            if !self.has_vacant_entries() {
+                // The new vacant entry is going to be the only one.
+                self.header_mut().last_vacant = at;
+            }
+            let (prev, next) = self.fetch_prev_and_next_vacant_entry(at);
+            self.entries
+                .put(at, Some(Entry::Vacant(VacantEntry { prev, next })));
+            self.header_mut().len_entries += 1;
+            self.update_neighboring_vacant_entry_links(prev, next, at);
+        }
     }
 
     /// Takes the element stored at the given index if any.
@@ -474,9 +681,13 @@ where
         match taken_entry {
             Entry::Occupied(value) => {
                 use core::cmp::min;
-                self.header.last_vacant =
-                    min(self.header.last_vacant, min(at, min(prev, next)));
-                self.header.len -= 1;
+                self.header_mut().last_vacant =
+                    min(self.header().last_vacant, min(at, min(prev, next)));
+                self.header_mut().len = self
+                    .header()
+                    .len
+                    .checked_sub(1)
+                    .expect("an occupied entry implies a non-empty storage stash");
                 Some(value)
             }
             Entry::Vacant { .. } => {
@@ -517,8 +728,13 @@ where
         self.entries.put(at, Some(new_vacant_entry));
         self.update_neighboring_vacant_entry_links(prev, next, at);
         use core::cmp::min;
-        self.header.last_vacant = min(self.header.last_vacant, min(at, min(prev, next)));
-        self.header.len -= 1;
+        self.header_mut().last_vacant =
+            min(self.header().last_vacant, min(at, min(prev, next)));
+        self.header_mut().len = self
+            .header()
+            .len
+            .checked_sub(1)
+            .expect("an occupied entry implies a non-empty storage stash");
         Some(())
     }
 
@@ -542,6 +758,8 @@ where
     ///   is optimally compacted.
     /// - Users are advised to call this method using `Some` concrete
     ///   value to keep gas costs within certain bounds.
+    /// - Without a bound on `max_iterations` this is a maintenance operation
+    ///   that is linear in the number of entries of the stash.
     /// - The call to the given callback takes place before the reinsertion
     ///   of the shifted occupied entry.
     pub fn defrag<C>(&mut self, max_iterations: Option<u32>, mut callback: C) -> u32
@@ -592,8 +810,116 @@ where
                     self.remove_vacant_entry(vacant_index, vacant_entry);
                 }
             }
-            self.header.len_entries -= 1;
+            self.header_mut().len_entries -= 1;
         }
         freed_cells
     }
+
+    /// Removes the vacant entries at the end of the stash.
+    ///
+    /// Returns the number of storage cells freed this way.
+    ///
+    /// # Note
+    ///
+    /// - Unlike [`Stash::defrag`] this does not move any element so indices
+    ///   stored outside of the stash stay valid.
+    /// - If the stash is empty all of its entries are removed so that
+    ///   subsequent calls to [`Stash::put`] start again at index `0`.
+    /// - This is a maintenance operation that is linear in the number of
+    ///   trailing vacant entries of the stash.
+    pub fn shrink_to_fit(&mut self) -> u32 {
+        if self.is_empty() {
+            // All entries are vacant so we can remove them without reading them.
+            let freed_cells = self.len_entries();
+            self.clear();
+            return freed_cells
+        }
+        let mut freed_cells = 0;
+        while let Some(index) = self.len_entries().checked_sub(1) {
+            let entry = self.entries.get(index).expect("index is out of bounds");
+            if entry.is_occupied() {
+                // Bail out at the last occupied entry.
+                break
+            }
+            match self
+                .entries
+                .put_get(index, None)
+                .expect("index is out of bounds")
+            {
+                Entry::Vacant(vacant_entry) => {
+                    self.remove_vacant_entry(index, vacant_entry);
+                }
+                Entry::Occupied(_) => {
+                    unreachable!("the removed entry is known to be vacant")
+                }
+            }
+            self.header_mut().len_entries -= 1;
+            freed_cells += 1;
+        }
+        freed_cells
+    }
+
+    /// Swaps the elements at the given indices.
+    ///
+    /// Returns `true` if the elements have been swapped and `false` if
+    /// one or both indices do not refer to an element of the stash in which
+    /// case the stash is not mutated.
+    pub fn swap(&mut self, a: Index, b: Index) -> bool {
+        if !self.contains(a) || !self.contains(b) {
+            return false
+        }
+        // Both entries are occupied so the links between the vacant entries
+        // are not affected by swapping them.
+        self.entries.swap(a, b);
+        true
+    }
+
+    /// Retains only the elements specified by the predicate.
+    ///
+    /// Removes all elements for which `f(index, &element)` returns `false`.
+    /// The indices of the retained elements do not change and the freed
+    /// entries are reused by subsequent calls to [`Stash::put`].
+    ///
+    /// # Note
+    ///
+    /// Avoid calling this method on big storage stashes since it visits
+    /// every element of the stash.
+    pub fn retain<F>(&mut self, mut f: F)
+    where
+        F: FnMut(Index, &T) -> bool,
+    {
+        let mut remaining = self.len();
+        for index in 0..self.len_entries() {
+            if remaining == 0 {
+                // Bail out early if all elements have been visited.
+                return
+            }
+            let keep = match self.get(index) {
+                Some(value) => {
+                    remaining -= 1;
+                    f(index, value)
+                }
+                None => continue,
+            };
+            if !keep {
+                self.take(index);
+            }
+        }
+    }
+
+    /// Removes all elements from the stash.
+    ///
+    /// # Note
+    ///
+    /// Use this method to clear the stash instead of e.g. iterative `take()`.
+    /// This method performs significantly better and does not actually read
+    /// any of the entries (whereas `take()` does).
+    pub fn clear(&mut self) {
+        for index in 0..self.len_entries() {
+            self.entries.put(index, None);
+        }
+        self.header_mut().last_vacant = 0;
+        self.header_mut().len = 0;
+        self.header_mut().len_entries = 0;
+    }
 }
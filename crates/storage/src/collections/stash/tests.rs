@@ -12,7 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::Stash as StorageStash;
+use super::{
+    OccupiedError,
+    Stash as StorageStash,
+    StashError,
+};
 use crate::traits::{
     KeyPtr,
     SpreadLayout,
@@ -38,6 +42,48 @@ fn regression_stash_unreachable_minified() {
     .unwrap()
 }
 
+#[test]
+#[should_panic(expected = "cannot put more elements into the storage stash")]
+fn put_into_full_stash_panics() {
+    let mut stash = <StorageStash<u8>>::new();
+    // Fake a stash that has no vacant entries and reached the maximum length.
+    stash.header_mut().len = u32::MAX;
+    stash.header_mut().len_entries = u32::MAX;
+    stash.header_mut().last_vacant = u32::MAX;
+    stash.put(b'A');
+}
+
+#[test]
+fn try_put_into_full_stash_fails() {
+    let mut stash = <StorageStash<u8>>::new();
+    // Fake a stash that has no vacant entries and reached the maximum length.
+    stash.header_mut().len = u32::MAX;
+    stash.header_mut().len_entries = u32::MAX;
+    stash.header_mut().last_vacant = u32::MAX;
+    assert_eq!(stash.try_put(b'A'), Err(StashError::Full));
+    // The stash has been left unchanged.
+    assert_eq!(stash.len(), u32::MAX);
+    assert_eq!(stash.len_entries(), u32::MAX);
+    assert_eq!(stash.last_vacant_index(), None);
+}
+
+#[test]
+fn put_into_almost_full_stash_works() {
+    let mut stash = <StorageStash<u8>>::new();
+    // Fake a stash that has room for exactly one more entry.
+    stash.header_mut().len = u32::MAX - 1;
+    stash.header_mut().len_entries = u32::MAX - 1;
+    stash.header_mut().last_vacant = u32::MAX - 1;
+    assert_eq!(stash.put(b'A'), u32::MAX - 1);
+    assert_eq!(stash.len(), u32::MAX);
+    assert_eq!(stash.get(u32::MAX - 1), Some(&b'A'));
+    assert_eq!(stash.try_put(b'B'), Err(StashError::Full));
+    // Taking the element again frees up its vacant entry for reuse.
+    assert_eq!(stash.take(u32::MAX - 1), Some(b'A'));
+    assert_eq!(stash.len(), u32::MAX - 1);
+    assert_eq!(stash.put(b'B'), u32::MAX - 1);
+}
+
 #[test]
 fn new_works() {
     // `StorageVec::new`
@@ -79,6 +125,98 @@ fn from_empty_iterator_works() {
     );
 }
 
+#[test]
+fn extend_works() {
+    // Extending an empty stash is the same as collecting into a stash.
+    let mut stash = <StorageStash<u8>>::new();
+    stash.extend([b'A', b'B', b'C'].iter().copied());
+    assert_eq!(
+        stash,
+        [b'A', b'B', b'C'].iter().copied().collect::<StorageStash<_>>()
+    );
+    // Extending a stash with vacant entries reuses them first.
+    let mut stash = create_holey_stash();
+    stash.extend([b'X', b'Y', b'Z', b'W'].iter().copied());
+    assert_eq!(stash.len(), 7);
+    assert_eq!(stash.len_entries(), 7);
+    assert_eq!(
+        stash.iter().copied().collect::<Vec<_>>(),
+        vec![b'X', b'B', b'Y', b'D', b'Z', b'F', b'W']
+    );
+    // Extending with an empty iterator does nothing.
+    stash.extend(core::iter::empty());
+    assert_eq!(stash.len(), 7);
+}
+
+#[test]
+fn peek_next_index_works() {
+    let mut stash = <StorageStash<u8>>::new();
+    assert_eq!(stash.peek_next_index(), 0);
+    for value in b'A'..=b'E' {
+        let expected = stash.peek_next_index();
+        assert_eq!(stash.put(value), expected);
+    }
+    assert_eq!(stash.peek_next_index(), 5);
+    // Taking elements makes their indices available again.
+    for &(take, expected) in &[(3, 3), (1, 1), (4, 1), (0, 0)] {
+        assert!(stash.take(take).is_some());
+        assert_eq!(stash.peek_next_index(), expected);
+    }
+    while stash.len() < stash.capacity() {
+        let expected = stash.peek_next_index();
+        assert_eq!(stash.put(b'X'), expected);
+    }
+    assert_eq!(stash.peek_next_index(), 5);
+    assert_eq!(stash.put(b'Z'), 5);
+}
+
+#[test]
+fn put_at_vacant_index_works() {
+    let mut stash = create_holey_stash();
+    // Fill the reclaimed vacant entries in an order different from `put`.
+    assert_eq!(stash.put_at(4, b'E'), Ok(()));
+    assert_eq!(stash.put_at(0, b'A'), Ok(()));
+    assert_eq!(stash.len(), 5);
+    assert_eq!(stash.len_entries(), 6);
+    assert_eq!(stash.get(4), Some(&b'E'));
+    assert_eq!(stash.get(0), Some(&b'A'));
+    // The remaining vacant entry is still reused by `put`.
+    assert_eq!(stash.put(b'C'), 2);
+    assert_eq!(stash.put(b'G'), 6);
+    assert_eq!(
+        stash.iter().copied().collect::<Vec<_>>(),
+        vec![b'A', b'B', b'C', b'D', b'E', b'F', b'G']
+    );
+}
+
+#[test]
+fn put_at_out_of_bounds_index_works() {
+    let mut stash = [b'A', b'B'].iter().copied().collect::<StorageStash<_>>();
+    assert_eq!(stash.put_at(5, b'F'), Ok(()));
+    assert_eq!(stash.len(), 3);
+    assert_eq!(stash.len_entries(), 6);
+    assert_eq!(stash.get(5), Some(&b'F'));
+    // The entries in between are vacant and reused by `put`.
+    for index in 2..5 {
+        assert_eq!(stash.get(index), None);
+    }
+    assert_eq!(stash.put_at(3, b'D'), Ok(()));
+    assert_eq!(stash.put(b'C'), 2);
+    assert_eq!(stash.put(b'E'), 4);
+    assert_eq!(stash.put(b'G'), 6);
+    assert_eq!(stash.len(), 7);
+    assert_eq!(stash.len_entries(), 7);
+}
+
+#[test]
+fn put_at_occupied_index_fails() {
+    let mut stash = create_holey_stash();
+    assert_eq!(stash.put_at(1, b'X'), Err(OccupiedError));
+    assert_eq!(stash.get(1), Some(&b'B'));
+    assert_eq!(stash.len(), 3);
+    assert_eq!(stash.len_entries(), 6);
+}
+
 #[test]
 fn take_from_filled_works() {
     let test_values = [b'A', b'B', b'C', b'D', b'E', b'F'];
@@ -192,6 +330,55 @@ fn get_works() {
     assert_eq!(stash.get_mut(1), None);
 }
 
+#[test]
+fn contains_works() {
+    let mut stash = [b'A', b'B', b'C']
+        .iter()
+        .copied()
+        .collect::<StorageStash<_>>();
+    assert!(stash.contains(0));
+    assert!(stash.contains(1));
+    assert!(stash.contains(2));
+    assert!(!stash.contains(3));
+    assert_eq!(stash.take(1), Some(b'B'));
+    assert!(stash.contains(0));
+    assert!(!stash.contains(1));
+    assert!(stash.contains(2));
+    assert!(!stash.contains(3));
+    assert_eq!(stash.put(b'D'), 1);
+    assert!(stash.contains(1));
+    // A stash with only vacant entries contains no elements.
+    let stash = create_vacant_stash();
+    for index in 0..stash.len_entries() {
+        assert!(!stash.contains(index));
+    }
+}
+
+#[test]
+fn get_mut_is_persisted() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let root_key = Key::from([0x42; 32]);
+        let stash1 = [b'A', b'B', b'C']
+            .iter()
+            .copied()
+            .collect::<StorageStash<_>>();
+        SpreadLayout::push_spread(&stash1, &mut KeyPtr::from(root_key));
+        let mut stash2 =
+            <StorageStash<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        *stash2.get_mut(1).expect("index is occupied") = b'X';
+        assert_eq!(stash2.get(1), Some(&b'X'));
+        SpreadLayout::push_spread(&stash2, &mut KeyPtr::from(root_key));
+        // The mutation has been written back under the same index.
+        let stash3 =
+            <StorageStash<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert_eq!(stash3.len(), 3);
+        assert_eq!(stash3.get(0), Some(&b'A'));
+        assert_eq!(stash3.get(1), Some(&b'X'));
+        assert_eq!(stash3.get(2), Some(&b'C'));
+        Ok(())
+    })
+}
+
 #[cfg(debug_assertions)]
 #[test]
 #[should_panic(expected = "index out of bounds: the len is 3 but the index is 3")]
@@ -267,6 +454,26 @@ fn iter_works() {
     assert_eq!(iter.count(), 0);
 }
 
+#[test]
+fn keys_and_values_work() {
+    let stash = create_holey_stash();
+    assert_eq!(stash.values().count(), stash.len() as usize);
+    assert_eq!(stash.keys().count(), stash.len() as usize);
+    assert_eq!(stash.keys().collect::<Vec<_>>(), vec![1, 3, 5]);
+    assert_eq!(stash.keys().rev().collect::<Vec<_>>(), vec![5, 3, 1]);
+    assert_eq!(stash.values().collect::<Vec<_>>(), vec![&b'B', &b'D', &b'F']);
+    // The keys are the indices assigned upon insertion.
+    let mut stash = <StorageStash<u8>>::new();
+    let indices = (b'A'..=b'E').map(|c| stash.put(c)).collect::<Vec<_>>();
+    assert_eq!(stash.keys().collect::<Vec<_>>(), indices);
+    for index in stash.keys() {
+        assert!(stash.get(index).is_some());
+    }
+    assert_eq!(stash.take(2), Some(b'C'));
+    assert_eq!(stash.keys().collect::<Vec<_>>(), vec![0, 1, 3, 4]);
+    assert_eq!(stash.values().count(), stash.len() as usize);
+}
+
 /// Create a stash that only has vacant entries.
 fn create_vacant_stash() -> StorageStash<u8> {
     let mut stash = [b'A', b'B', b'C']
@@ -295,6 +502,135 @@ fn create_holey_stash() -> StorageStash<u8> {
     stash
 }
 
+#[test]
+fn swap_works() {
+    let mut stash = create_holey_stash();
+    // Swapping two occupied entries:
+    assert!(stash.swap(1, 3));
+    assert_eq!(stash.get(1), Some(&b'D'));
+    assert_eq!(stash.get(3), Some(&b'B'));
+    assert!(stash.swap(5, 5));
+    assert_eq!(stash.get(5), Some(&b'F'));
+    // Swapping with a vacant or out of bounds entry does nothing:
+    assert!(!stash.swap(1, 2));
+    assert!(!stash.swap(0, 3));
+    assert!(!stash.swap(1, 6));
+    assert_eq!(
+        stash.iter().copied().collect::<Vec<_>>(),
+        vec![b'D', b'B', b'F']
+    );
+    // The vacant entries are still reused.
+    assert_eq!(stash.len(), 3);
+    assert_eq!(stash.put(b'X'), 0);
+}
+
+#[test]
+fn swap_is_persisted() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let root_key = Key::from([0x42; 32]);
+        let stash1 = create_holey_stash();
+        SpreadLayout::push_spread(&stash1, &mut KeyPtr::from(root_key));
+        let mut stash2 =
+            <StorageStash<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert!(stash2.swap(1, 5));
+        SpreadLayout::push_spread(&stash2, &mut KeyPtr::from(root_key));
+        let stash3 =
+            <StorageStash<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert_eq!(stash3.get(1), Some(&b'F'));
+        assert_eq!(stash3.get(3), Some(&b'D'));
+        assert_eq!(stash3.get(5), Some(&b'B'));
+        Ok(())
+    })
+}
+
+#[test]
+fn retain_works() {
+    let mut stash = [b'A', b'B', b'C', b'D', b'E', b'F']
+        .iter()
+        .copied()
+        .collect::<StorageStash<_>>();
+    let mut visited = Vec::new();
+    stash.retain(|index, value| {
+        visited.push((index, *value));
+        index % 2 == 0
+    });
+    assert_eq!(
+        visited,
+        vec![(0, b'A'), (1, b'B'), (2, b'C'), (3, b'D'), (4, b'E'), (5, b'F')]
+    );
+    assert_eq!(stash.len(), 3);
+    assert_eq!(stash.len_entries(), 6);
+    assert_eq!(
+        stash.iter().copied().collect::<Vec<_>>(),
+        vec![b'A', b'C', b'E']
+    );
+    // Retained elements keep their indices.
+    assert_eq!(stash.get(0), Some(&b'A'));
+    assert_eq!(stash.get(2), Some(&b'C'));
+    assert_eq!(stash.get(4), Some(&b'E'));
+    // Subsequent puts reuse the freed entries.
+    let mut reused = vec![stash.put(b'X'), stash.put(b'Y'), stash.put(b'Z')];
+    reused.sort_unstable();
+    assert_eq!(reused, vec![1, 3, 5]);
+    assert_eq!(stash.len_entries(), 6);
+    assert_eq!(stash.put(b'W'), 6);
+    // Retaining nothing yields an empty stash.
+    stash.retain(|_, _| false);
+    assert!(stash.is_empty());
+    assert_eq!(stash.iter().next(), None);
+}
+
+#[test]
+fn clear_works() {
+    // Clearing an empty stash is a no-op.
+    let mut stash = <StorageStash<u8>>::new();
+    stash.clear();
+    assert_eq!(stash, StorageStash::new());
+    // Clear a stash with interleaved vacant and occupied entries.
+    let mut stash = create_holey_stash();
+    stash.clear();
+    assert!(stash.is_empty());
+    assert_eq!(stash.len_entries(), 0);
+    assert_eq!(stash.iter().next(), None);
+    assert_eq!(stash, StorageStash::new());
+    // A cleared stash can be used as usual.
+    assert_eq!(stash.put(b'A'), 0);
+    assert_eq!(stash.put(b'B'), 1);
+    assert_eq!(stash.get(0), Some(&b'A'));
+    assert_eq!(stash.get(1), Some(&b'B'));
+}
+
+#[test]
+fn clear_removes_entries_from_storage() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let root_key = Key::from([0x42; 32]);
+        let stash1 = create_holey_stash();
+        let len_entries = stash1.len_entries();
+        SpreadLayout::push_spread(&stash1, &mut KeyPtr::from(root_key));
+        let mut stash2 =
+            <StorageStash<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        stash2.clear();
+        SpreadLayout::push_spread(&stash2, &mut KeyPtr::from(root_key));
+        // All entries of the stash have been removed from the contract storage.
+        for index in 0..len_entries {
+            let key = stash2
+                .entries
+                .key_at(index)
+                .expect("the stash has been pulled from storage");
+            assert_eq!(
+                ink_env::get_contract_storage::<super::Entry<u8>>(&key)
+                    .map(|entry| entry.is_some()),
+                Ok(false)
+            );
+        }
+        let stash3 =
+            <StorageStash<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert!(stash3.is_empty());
+        assert_eq!(stash3, StorageStash::new());
+        Ok(())
+    })
+}
+
 #[test]
 fn iter_over_vacant_works() {
     let stash = create_vacant_stash();
@@ -362,6 +698,171 @@ fn iter_rev_over_holey_works() {
     assert_eq!(iter.count(), 0);
 }
 
+#[test]
+fn iter_alternating_next_and_next_back_works() {
+    // Create a stash with interior vacant entries:
+    //
+    //    i | 0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 |
+    //  val | A |   | C | D |   |   | G | H |
+    let mut stash = [b'A', b'B', b'C', b'D', b'E', b'F', b'G', b'H']
+        .iter()
+        .copied()
+        .collect::<StorageStash<_>>();
+    for &index in &[1, 4, 5] {
+        assert!(stash.take(index).is_some());
+    }
+    // Test iterator over shared references.
+    let mut iter = stash.iter();
+    assert_eq!(iter.size_hint(), (5, Some(5)));
+    assert_eq!(iter.next(), Some(&b'A'));
+    assert_eq!(iter.next_back(), Some(&b'H'));
+    assert_eq!(iter.size_hint(), (3, Some(3)));
+    assert_eq!(iter.next(), Some(&b'C'));
+    assert_eq!(iter.next_back(), Some(&b'G'));
+    assert_eq!(iter.size_hint(), (1, Some(1)));
+    assert_eq!(iter.next_back(), Some(&b'D'));
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+    assert_eq!(iter.next(), None);
+    assert_eq!(iter.next_back(), None);
+    // Test iterator over exclusive references.
+    let mut iter = stash.iter_mut();
+    assert_eq!(iter.next_back(), Some(&mut b'H'));
+    assert_eq!(iter.next(), Some(&mut b'A'));
+    assert_eq!(iter.next_back(), Some(&mut b'G'));
+    assert_eq!(iter.size_hint(), (2, Some(2)));
+    assert_eq!(iter.next(), Some(&mut b'C'));
+    assert_eq!(iter.next(), Some(&mut b'D'));
+    assert_eq!(iter.size_hint(), (0, Some(0)));
+    assert_eq!(iter.next_back(), None);
+    assert_eq!(iter.next(), None);
+}
+
+#[test]
+fn for_loop_over_references_works() {
+    let mut stash = create_holey_stash();
+    let mut visited = Vec::new();
+    for value in &stash {
+        visited.push(value);
+    }
+    assert_eq!(visited, stash.iter().collect::<Vec<_>>());
+    for value in &mut stash {
+        *value = value.to_ascii_lowercase();
+    }
+    assert_eq!(stash.iter().collect::<Vec<_>>(), vec![&b'b', &b'd', &b'f']);
+}
+
+#[test]
+fn iter_len_decreases_and_iter_is_fused() {
+    let stash = create_holey_stash();
+    let mut iter = stash.iter();
+    for remaining in (0..stash.len() as usize).rev() {
+        assert!(iter.next().is_some());
+        assert_eq!(iter.len(), remaining);
+        assert_eq!(iter.size_hint(), (remaining, Some(remaining)));
+    }
+    // Exhausted iterators keep yielding `None`.
+    for _ in 0..3 {
+        assert_eq!(iter.next(), None);
+        assert_eq!(iter.next_back(), None);
+        assert_eq!(iter.len(), 0);
+    }
+    assert_eq!(stash.iter().collect::<Vec<_>>(), vec![&b'B', &b'D', &b'F']);
+}
+
+#[test]
+fn iter_mut_is_persisted() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let root_key = Key::from([0x42; 32]);
+        let stash1 = create_holey_stash();
+        SpreadLayout::push_spread(&stash1, &mut KeyPtr::from(root_key));
+        let mut stash2 =
+            <StorageStash<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        let mut iter = stash2.iter_mut();
+        assert_eq!(iter.size_hint(), (3, Some(3)));
+        for value in &mut iter {
+            *value += 1;
+        }
+        assert_eq!(iter.size_hint(), (0, Some(0)));
+        SpreadLayout::push_spread(&stash2, &mut KeyPtr::from(root_key));
+        // The mutations have been written back under the same indices.
+        let stash3 =
+            <StorageStash<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert_eq!(
+            stash3.iter().copied().collect::<Vec<_>>(),
+            vec![b'C', b'E', b'G']
+        );
+        assert_eq!(stash3.get(1), Some(&b'C'));
+        assert_eq!(stash3.get(3), Some(&b'E'));
+        assert_eq!(stash3.get(5), Some(&b'G'));
+        Ok(())
+    })
+}
+
+#[test]
+fn iter_prefetched_works() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let root_key = Key::from([0x42; 32]);
+        let stash1 = create_holey_stash();
+        SpreadLayout::push_spread(&stash1, &mut KeyPtr::from(root_key));
+        let pull_stash = || {
+            <StorageStash<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key))
+        };
+        for batch in 0..=7 {
+            // Load the elements one at a time from another instance.
+            let stash2 = pull_stash();
+            let expected = (0..stash2.len_entries())
+                .filter_map(|index| stash2.get(index).copied())
+                .collect::<Vec<_>>();
+            assert_eq!(expected, vec![b'B', b'D', b'F']);
+            let stash3 = pull_stash();
+            assert_eq!(
+                stash3.iter_prefetched(batch).copied().collect::<Vec<_>>(),
+                expected
+            );
+            assert_eq!(
+                stash3.iter_prefetched(batch).rev().copied().collect::<Vec<_>>(),
+                expected.iter().rev().copied().collect::<Vec<_>>()
+            );
+            let stash4 = pull_stash();
+            let mut iter = stash4.iter_prefetched(batch);
+            assert_eq!(iter.next(), Some(&b'B'));
+            assert_eq!(iter.next_back(), Some(&b'F'));
+            assert_eq!(iter.next(), Some(&b'D'));
+            assert_eq!(iter.next(), None);
+            // We have to forget the pulled instances because their `Drop`
+            // implementations would otherwise clear the shared storage region.
+            core::mem::forget(stash2);
+            core::mem::forget(stash3);
+            core::mem::forget(stash4);
+        }
+        // Prefetching loads the whole batch upon yielding the first element.
+        let contract = ink_env::test::get_current_contract_account_id::<
+            ink_env::DefaultEnvironment,
+        >()?;
+        let count_reads = || {
+            ink_env::test::get_contract_storage_rw::<ink_env::DefaultEnvironment>(
+                &contract,
+            )
+            .map(|(reads, _writes)| reads)
+        };
+        let stash5 = pull_stash();
+        let mut iter = stash5.iter();
+        let reads = count_reads()?;
+        assert_eq!(iter.next(), Some(&b'B'));
+        assert_eq!(count_reads()? - reads, 2);
+        let stash6 = pull_stash();
+        let mut iter = stash6.iter_prefetched(4);
+        let reads = count_reads()?;
+        assert_eq!(iter.next(), Some(&b'B'));
+        assert_eq!(count_reads()? - reads, 4);
+        assert_eq!(iter.next(), Some(&b'D'));
+        assert_eq!(count_reads()? - reads, 4);
+        core::mem::forget(stash5);
+        core::mem::forget(stash6);
+        Ok(())
+    })
+}
+
 #[test]
 fn iter_rev_works() {
     let stash = [b'A', b'B', b'C']
@@ -440,6 +941,149 @@ fn simple_defrag_works() {
     );
 }
 
+#[test]
+fn defrag_shrinks_capacity_and_storage() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let root_key = Key::from([0x42; 32]);
+        let mut stash = (0..100u8).collect::<StorageStash<_>>();
+        for index in (0..100).filter(|&index| index != 42) {
+            assert_eq!(stash.take(index), Some(index as u8));
+        }
+        assert_eq!(stash.len(), 1);
+        assert_eq!(stash.capacity(), 100);
+        SpreadLayout::push_spread(&stash, &mut KeyPtr::from(root_key));
+        let mut stash2 =
+            <StorageStash<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        let mut moves = std::collections::BTreeMap::new();
+        assert_eq!(
+            stash2.defrag(None, |from, to, _| {
+                moves.insert(from, to);
+            }),
+            99
+        );
+        // The only remaining element has been moved to the front.
+        assert_eq!(moves.into_iter().collect::<Vec<_>>(), vec![(42, 0)]);
+        assert_eq!(stash2.capacity(), 1);
+        assert_eq!(stash2.get(0), Some(&42));
+        SpreadLayout::push_spread(&stash2, &mut KeyPtr::from(root_key));
+        // The freed entries have been removed from the contract storage.
+        for index in 1..100 {
+            let key = stash2
+                .entries
+                .key_at(index)
+                .expect("the stash has been pulled from storage");
+            assert_eq!(
+                ink_env::get_contract_storage::<super::Entry<u8>>(&key)
+                    .map(|entry| entry.is_some()),
+                Ok(false)
+            );
+        }
+        let stash3 =
+            <StorageStash<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert_eq!(stash3.capacity(), 1);
+        assert_eq!(stash3.iter().copied().collect::<Vec<_>>(), vec![42]);
+        Ok(())
+    })
+}
+
+#[test]
+fn drain_works() {
+    let mut stash = create_holey_stash();
+    let mut drain = stash.drain();
+    assert_eq!(drain.len(), 3);
+    assert_eq!(drain.next(), Some((1, b'B')));
+    assert_eq!(drain.len(), 2);
+    assert_eq!(drain.collect::<Vec<_>>(), vec![(3, b'D'), (5, b'F')]);
+    assert!(stash.is_empty());
+    assert_eq!(stash.capacity(), 0);
+    assert_eq!(stash.put(b'X'), 0);
+}
+
+#[test]
+fn drain_dropped_early_removes_all_elements() {
+    let mut stash = create_holey_stash();
+    assert_eq!(stash.drain().next(), Some((1, b'B')));
+    assert!(stash.is_empty());
+    assert_eq!(stash.capacity(), 0);
+    assert_eq!(stash.iter().next(), None);
+    // An untouched draining iterator removes all elements as well.
+    stash.extend(b"XYZ".iter().copied());
+    drop(stash.drain());
+    assert!(stash.is_empty());
+    assert_eq!(stash.drain().next(), None);
+}
+
+#[test]
+fn drain_is_persisted() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let root_key = Key::from([0x42; 32]);
+        let stash = create_holey_stash();
+        SpreadLayout::push_spread(&stash, &mut KeyPtr::from(root_key));
+        let mut stash2 =
+            <StorageStash<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        let drained = stash2.drain().map(|(_, value)| value).collect::<Vec<_>>();
+        assert_eq!(drained, vec![b'B', b'D', b'F']);
+        SpreadLayout::push_spread(&stash2, &mut KeyPtr::from(root_key));
+        let stash3 =
+            <StorageStash<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert!(stash3.is_empty());
+        assert_eq!(stash3.capacity(), 0);
+        Ok(())
+    })
+}
+
+#[test]
+fn shrink_to_fit_removes_trailing_vacant_entries() {
+    // Create a stash with interior and trailing vacant entries:
+    //
+    //    i | 0 | 1 | 2 | 3 | 4 | 5 |
+    //  val | A |   | C |   |   |   |
+    let mut stash = [b'A', b'B', b'C', b'D', b'E', b'F']
+        .iter()
+        .copied()
+        .collect::<StorageStash<_>>();
+    for &index in &[4, 1, 5, 3] {
+        assert!(stash.take(index).is_some());
+    }
+    assert_eq!(stash.capacity(), 6);
+    assert_eq!(stash.shrink_to_fit(), 3);
+    assert_eq!(stash.capacity(), 3);
+    // The remaining elements have not been moved.
+    assert_eq!(stash.get(0), Some(&b'A'));
+    assert_eq!(stash.get(2), Some(&b'C'));
+    assert_eq!(stash.shrink_to_fit(), 0);
+    // The interior vacant entry is reused before pushing new entries.
+    assert_eq!(stash.put(b'X'), 1);
+    assert_eq!(stash.put(b'Y'), 3);
+    assert_eq!(stash.iter().copied().collect::<Vec<_>>(), vec![b'A', b'X', b'C', b'Y']);
+}
+
+#[test]
+fn shrink_to_fit_resets_drained_stash() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let root_key = Key::from([0x42; 32]);
+        let mut stash = (0..10u8).collect::<StorageStash<_>>();
+        for index in (0..10).rev() {
+            assert_eq!(stash.take(index), Some(index as u8));
+        }
+        assert!(stash.is_empty());
+        assert_eq!(stash.capacity(), 10);
+        assert_eq!(stash.shrink_to_fit(), 10);
+        assert_eq!(stash.capacity(), 0);
+        assert_eq!(stash.peek_next_index(), 0);
+        // Refilling the stash starts again at index `0`.
+        for (index, value) in (b'A'..=b'C').enumerate() {
+            assert_eq!(stash.put(value), index as u32);
+        }
+        SpreadLayout::push_spread(&stash, &mut KeyPtr::from(root_key));
+        let stash2 =
+            <StorageStash<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert_eq!(stash2.capacity(), 3);
+        assert_eq!(stash2.iter().copied().collect::<Vec<_>>(), vec![b'A', b'B', b'C']);
+        Ok(())
+    })
+}
+
 /// Returns a storage stash that looks internally like this:
 ///
 ///    i | 0 | 1 | 2 | 3 | 4 | 5 | 6 | 7 |
@@ -748,3 +1392,77 @@ fn spread_layout_clear_works() {
     })
     .unwrap()
 }
+
+#[test]
+fn untouched_stash_is_not_loaded_or_stored() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let contract = ink_env::test::get_current_contract_account_id::<
+            ink_env::DefaultEnvironment,
+        >()?;
+        let storage_rw = || {
+            ink_env::test::get_contract_storage_rw::<ink_env::DefaultEnvironment>(
+                &contract,
+            )
+        };
+        let root_key = Key::from([0x42; 32]);
+        let stash1 = create_holey_stash();
+        SpreadLayout::push_spread(&stash1, &mut KeyPtr::from(root_key));
+        let rw = storage_rw()?;
+        // Pulling and pushing an untouched stash neither reads nor writes.
+        let stash2 =
+            <StorageStash<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        SpreadLayout::push_spread(&stash2, &mut KeyPtr::from(root_key));
+        assert_eq!(storage_rw()?, rw);
+        // Querying a new stash does not read either.
+        let mut stash3 = <StorageStash<u8>>::new();
+        assert!(stash3.is_empty());
+        assert_eq!(stash3.put(b'A'), 0);
+        assert_eq!(stash3.len(), 1);
+        assert_eq!(storage_rw()?, rw);
+        // A pulled stash loads its header upon first use.
+        let mut stash4 =
+            <StorageStash<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert_eq!(stash4.len(), 3);
+        assert_eq!(stash4.take(1), Some(b'B'));
+        assert_eq!(stash4.len(), 2);
+        assert_ne!(storage_rw()?, rw);
+        // We have to forget `stash2` because its `Drop` implementation would
+        // otherwise try to load its entries from the storage region that
+        // `stash4` already cleared upon its own drop.
+        core::mem::forget(stash2);
+        Ok(())
+    })
+}
+
+#[test]
+fn spread_layout_composes_with_other_collections() -> ink_env::Result<()> {
+    use crate::collections::HashMap as StorageHashMap;
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let root_key = Key::from([0x42; 32]);
+        // A contract storage holding a stash alongside a hash map.
+        type Storage = (StorageStash<u8>, StorageHashMap<u8, i32>);
+        let stash = create_holey_stash();
+        let hmap = [(b'A', 1), (b'B', 2)]
+            .iter()
+            .copied()
+            .collect::<StorageHashMap<u8, i32>>();
+        let storage1: Storage = (stash, hmap);
+        SpreadLayout::push_spread(&storage1, &mut KeyPtr::from(root_key));
+        let mut storage2 =
+            <Storage as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert_eq!(storage1, storage2);
+        // Mutate both collections and check that they do not interfere.
+        assert_eq!(storage2.0.put(b'X'), 0);
+        assert_eq!(storage2.1.insert(b'C', 3), None);
+        SpreadLayout::push_spread(&storage2, &mut KeyPtr::from(root_key));
+        let storage3 =
+            <Storage as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert_eq!(storage2, storage3);
+        assert_eq!(
+            storage3.0.iter().copied().collect::<Vec<_>>(),
+            vec![b'X', b'B', b'D', b'F']
+        );
+        assert_eq!(storage3.1.get(&b'C'), Some(&3));
+        Ok(())
+    })
+}
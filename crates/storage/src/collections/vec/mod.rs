@@ -279,7 +279,7 @@ where
     ///
     /// This operation does not preserve ordering but is constant time.
     pub fn swap_remove(&mut self, n: u32) -> Option<T> {
-        if self.is_empty() {
+        if n >= self.len() {
             return None
         }
         self.elems.swap(n, self.len() - 1);
@@ -290,6 +290,7 @@ where
     ///
     /// The last element of the vector is put into the indexed slot.
     /// Returns `Some(())` if an element has been removed and `None` otherwise.
+    /// Does not mutate the vector if the index is out of bounds.
     ///
     /// # Note
     ///
@@ -297,7 +298,7 @@ where
     /// no need to return the removed element since it avoids a contract storage
     /// read for some use cases.
     pub fn swap_remove_drop(&mut self, n: u32) -> Option<()> {
-        if self.is_empty() {
+        if n >= self.len() {
             return None
         }
         self.elems.put(n, None);
@@ -357,6 +357,20 @@ fn swap_remove_drop_works() {
     assert_eq_slice(&vec, &[]);
 }
 
+#[test]
+fn swap_remove_out_of_bounds_works() {
+    let mut vec = vec_from_slice(&[b'a', b'b', b'c']);
+    // Out of bounds indices do not mutate the vector.
+    assert_eq!(vec.swap_remove(3), None);
+    assert_eq_slice(&vec, &[b'a', b'b', b'c']);
+    assert_eq!(vec.swap_remove(u32::MAX), None);
+    assert_eq_slice(&vec, &[b'a', b'b', b'c']);
+    assert_eq!(vec.swap_remove_drop(3), None);
+    assert_eq_slice(&vec, &[b'a', b'b', b'c']);
+    assert_eq!(vec.swap_remove_drop(u32::MAX), None);
+    assert_eq_slice(&vec, &[b'a', b'b', b'c']);
+}
+
 #[test]
 fn spread_layout_push_pull_works() -> ink_env::Result<()> {
     ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
@@ -425,3 +439,27 @@ fn clear_works_on_empty_vec() {
     vec.clear();
     assert!(vec.is_empty());
 }
+
+#[test]
+fn push_pop_is_persisted_in_order() -> ink_env::Result<()> {
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        let root_key = Key::from([0x42; 32]);
+        let vec1 = vec_from_slice(&[b'a', b'b', b'c']);
+        SpreadLayout::push_spread(&vec1, &mut KeyPtr::from(root_key));
+        let mut vec2 =
+            <StorageVec<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        assert_eq_slice(&vec2, &[b'a', b'b', b'c']);
+        // Pop and push within another execution.
+        assert_eq!(vec2.pop(), Some(b'c'));
+        vec2.push(b'd');
+        vec2.push(b'e');
+        SpreadLayout::push_spread(&vec2, &mut KeyPtr::from(root_key));
+        let vec3 =
+            <StorageVec<u8> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+        // Elements are kept densely in their insertion order.
+        assert_eq_slice(&vec3, &[b'a', b'b', b'd', b'e']);
+        assert_eq!(vec3.get(2), Some(&b'd'));
+        assert_eq!(vec3.get(4), None);
+        Ok(())
+    })
+}
@@ -0,0 +1,151 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the `SpreadLayout`, `PackedLayout` and `StorageLayout` derives
+//! applied to multi-field storage structs.
+
+use crate::{
+    collections::Vec as StorageVec,
+    traits::{
+        pull_spread_root,
+        push_spread_root,
+        KeyPtr,
+        PackedLayout,
+        SpreadLayout,
+        StorageLayout,
+    },
+};
+use ink_metadata::layout::Layout;
+use ink_primitives::Key;
+
+#[derive(
+    Debug,
+    Clone,
+    PartialEq,
+    Eq,
+    scale::Encode,
+    scale::Decode,
+    scale_info::TypeInfo,
+    SpreadLayout,
+    PackedLayout,
+    StorageLayout,
+)]
+struct Account {
+    owner: [u8; 32],
+    balance: u128,
+    nonce: u32,
+}
+
+#[derive(Debug, PartialEq, Eq, SpreadLayout, StorageLayout)]
+struct Token {
+    total_supply: u128,
+    accounts: StorageVec<Account>,
+    paused: bool,
+}
+
+fn account(owner: u8, balance: u128) -> Account {
+    Account {
+        owner: [owner; 32],
+        balance,
+        nonce: 0,
+    }
+}
+
+fn token() -> Token {
+    Token {
+        total_supply: 1_000,
+        accounts: [account(0x01, 600), account(0x02, 400)].iter().cloned().collect(),
+        paused: false,
+    }
+}
+
+fn run_test<F>(f: F)
+where
+    F: FnOnce(),
+{
+    ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+        f();
+        Ok(())
+    })
+    .unwrap()
+}
+
+#[test]
+fn derived_footprint_is_sum_of_fields() {
+    assert_eq!(
+        <Account as SpreadLayout>::FOOTPRINT,
+        <[u8; 32] as SpreadLayout>::FOOTPRINT
+            + <u128 as SpreadLayout>::FOOTPRINT
+            + <u32 as SpreadLayout>::FOOTPRINT
+    );
+    assert_eq!(
+        <Token as SpreadLayout>::FOOTPRINT,
+        <u128 as SpreadLayout>::FOOTPRINT
+            + <StorageVec<Account> as SpreadLayout>::FOOTPRINT
+            + <bool as SpreadLayout>::FOOTPRINT
+    );
+}
+
+#[test]
+fn derived_spread_layout_push_pull_works() {
+    run_test(|| {
+        let root_key = Key::from([0x42; 32]);
+        let token1 = token();
+        push_spread_root(&token1, &root_key);
+        let token2 = pull_spread_root::<Token>(&root_key);
+        assert_eq!(token1, token2);
+        assert_eq!(token2.accounts.get(1), Some(&account(0x02, 400)));
+    })
+}
+
+#[test]
+fn derived_spread_layout_follows_declaration_order() {
+    run_test(|| {
+        let root_key = Key::from([0x42; 32]);
+        push_spread_root(&token(), &root_key);
+        // Fields are laid out one after another starting at the root key.
+        let mut ptr = KeyPtr::from(root_key);
+        assert_eq!(<u128 as SpreadLayout>::pull_spread(&mut ptr), 1_000);
+        let accounts = <StorageVec<Account> as SpreadLayout>::pull_spread(&mut ptr);
+        assert_eq!(accounts.len(), 2);
+        assert!(!<bool as SpreadLayout>::pull_spread(&mut ptr));
+    })
+}
+
+#[test]
+#[should_panic(expected = "storage entry was empty")]
+fn derived_spread_layout_clear_works() {
+    run_test(|| {
+        let root_key = Key::from([0x42; 32]);
+        let token = token();
+        push_spread_root(&token, &root_key);
+        SpreadLayout::clear_spread(&token, &mut KeyPtr::from(root_key));
+        let _ = pull_spread_root::<Token>(&root_key);
+    })
+}
+
+#[test]
+fn derived_storage_layout_lists_fields_in_order() {
+    let root_key = Key::from([0x00; 32]);
+    let layout = <Token as StorageLayout>::layout(&mut KeyPtr::from(root_key));
+    let fields = match layout {
+        Layout::Struct(struct_layout) => struct_layout
+            .fields()
+            .iter()
+            .map(|field| field.name().copied())
+            .collect::<Vec<_>>(),
+        _ => panic!("expected a struct layout"),
+    };
+    assert_eq!(fields, vec![Some("total_supply"), Some("accounts"), Some("paused")]);
+}
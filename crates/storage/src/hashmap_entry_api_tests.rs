@@ -106,6 +106,10 @@ macro_rules! gen_tests_for_backend {
                 // then
                 let hmap3 = pull_hmap();
                 assert_eq!(hmap3.get(&b'A'), Some(&14));
+                // We have to forget `hmap2` because the `Drop` implementation of
+                // its untouched keys would otherwise try to load their lazy header
+                // from the storage region that `hmap3` already cleared upon drop.
+                core::mem::forget(hmap2);
                 Ok(())
             })
         }
@@ -392,6 +396,10 @@ macro_rules! gen_tests_for_backend {
                 // insert is was marked as `Mutated`.
                 let hmap3 = pull_hmap();
                 assert_eq!(hmap3.get(&b'A'), Some(&999));
+                // We have to forget `hmap2` because the `Drop` implementation of
+                // its untouched keys would otherwise try to load their lazy header
+                // from the storage region that `hmap3` already cleared upon drop.
+                core::mem::forget(hmap2);
 
                 Ok(())
             })
@@ -311,6 +311,87 @@ where
         self.load_entry_mut().value_mut().into()
     }
 
+    /// Takes the value out of this cell leaving it empty.
+    ///
+    /// # Note
+    ///
+    /// This eventually lazily loads the value from the contract storage.
+    /// The associated contract storage is cleared upon the next push.
+    ///
+    /// # Panics
+    ///
+    /// If decoding the loaded value to `T` failed.
+    pub fn take(&mut self) -> Option<T> {
+        self.load_entry_mut().put(None)
+    }
+
+    /// Mutates the value in this cell through `f` and returns its result.
+    ///
+    /// Returns `None` and does not call `f` if the cell is empty.
+    ///
+    /// # Note
+    ///
+    /// This eventually lazily loads the value from the contract storage.
+    /// Unlike [`LazyCell::get_mut`] the cell is only marked as mutated,
+    /// and thus only written back to the contract storage, if `f` actually
+    /// changed the value.
+    ///
+    /// In order to detect changes the value is cloned before and compared after
+    /// calling `f`. For big values this can be more expensive than writing them
+    /// back unconditionally via [`LazyCell::get_mut`].
+    ///
+    /// # Panics
+    ///
+    /// If decoding the loaded value to `T` failed.
+    pub fn mutate_with<F, R>(&mut self, f: F) -> Option<R>
+    where
+        T: Clone + PartialEq,
+        F: FnOnce(&mut T) -> R,
+    {
+        // SAFETY: We load the entry either from cache or from contract storage.
+        //
+        //         This is safe because we are just using an exclusive reference
+        //         from within a `&mut self` method. The state of the entry
+        //         is restored below if the loaded value has not been changed.
+        let entry = unsafe { &mut *self.load_through_cache().as_ptr() };
+        // Bail out before touching the state so that empty cells stay untouched.
+        let old_value = entry.value().clone()?;
+        let old_state = entry.replace_state(EntryState::Mutated);
+        let value = entry
+            .value_mut()
+            .as_mut()
+            .expect("the entry has been checked to be occupied");
+        let result = f(value);
+        if *value == old_value {
+            entry.replace_state(old_state);
+        }
+        Some(result)
+    }
+
+    /// Mutates the value in this cell through `f` and returns its result.
+    ///
+    /// Populates the cell with the default value first if it is empty.
+    ///
+    /// # Note
+    ///
+    /// This behaves like [`LazyCell::mutate_with`] for occupied cells.
+    /// Empty cells are always written back to the contract storage.
+    ///
+    /// # Panics
+    ///
+    /// If decoding the loaded value to `T` failed.
+    pub fn mutate_or_default<F, R>(&mut self, f: F) -> R
+    where
+        T: Default + Clone + PartialEq,
+        F: FnOnce(&mut T) -> R,
+    {
+        if self.get().is_none() {
+            self.set(Default::default());
+        }
+        self.mutate_with(f)
+            .expect("the cell has been populated with the default value")
+    }
+
     /// Sets the value in this cell to `value`, without executing any reads.
     ///
     /// # Note
@@ -341,6 +422,35 @@ where
     }
 }
 
+impl<T> LazyCell<T>
+where
+    T: SpreadLayout + PartialEq,
+{
+    /// Sets the value in this cell to `value` unless it is equal to the cached value.
+    ///
+    /// # Note
+    ///
+    /// No reads from contract storage will be executed.
+    ///
+    /// Unlike [`LazyCell::set`] the cell stays untouched, and thus is not
+    /// written back to the contract storage, if the cell already caches a value
+    /// equal to `new_value`. If nothing has been cached so far this behaves
+    /// exactly like [`LazyCell::set`].
+    ///
+    /// # Panics
+    ///
+    /// If accessing the inner value fails.
+    #[inline]
+    pub fn set_if_changed(&mut self, new_value: T) {
+        if let Some(entry) = self.entry() {
+            if entry.value().as_ref() == Some(&new_value) {
+                return
+            }
+        }
+        self.set(new_value)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{
@@ -414,6 +524,199 @@ mod tests {
         assert_eq!(cell.get(), Some(&2));
     }
 
+    #[test]
+    fn mutate_or_default_works() -> ink_env::Result<()> {
+        run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let root_key = Key::from([0x42; 32]);
+            // Empty cells are populated with the default value.
+            let mut cell = <LazyCell<i32>>::lazy(root_key);
+            assert_eq!(cell.mutate_or_default(|value| *value), 0);
+            assert_eq!(
+                cell.entry(),
+                Some(&StorageEntry::new(Some(0), EntryState::Mutated))
+            );
+            assert_eq!(
+                cell.mutate_or_default(|value| {
+                    *value += 5;
+                    *value
+                }),
+                5
+            );
+            SpreadLayout::push_spread(&cell, &mut KeyPtr::from(root_key));
+            assert_eq!(ink_env::get_contract_storage::<i32>(&root_key), Ok(Some(5)));
+            // Occupied cells are loaded once and written back once.
+            let mut cell =
+                <LazyCell<i32> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+            cell.mutate_or_default(|value| *value += 1);
+            ink_env::set_contract_storage(&root_key, &0i32);
+            cell.mutate_or_default(|value| *value += 1);
+            assert_eq!(cell.get(), Some(&7));
+            SpreadLayout::push_spread(&cell, &mut KeyPtr::from(root_key));
+            assert_eq!(ink_env::get_contract_storage::<i32>(&root_key), Ok(Some(7)));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn take_works() -> ink_env::Result<()> {
+        run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut cell = <LazyCell<i32>>::new(Some(1));
+            assert_eq!(cell.take(), Some(1));
+            assert_eq!(cell.get(), None);
+            assert_eq!(cell.take(), None);
+            // Taking the value out of a pulled cell clears its storage.
+            let root_key = Key::from([0x42; 32]);
+            let cell = LazyCell::new(Some(1i32));
+            SpreadLayout::push_spread(&cell, &mut KeyPtr::from(root_key));
+            let mut cell =
+                <LazyCell<i32> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+            assert_eq!(cell.take(), Some(1));
+            assert_eq!(
+                cell.entry(),
+                Some(&StorageEntry::new(None, EntryState::Mutated))
+            );
+            SpreadLayout::push_spread(&cell, &mut KeyPtr::from(root_key));
+            let cell2 = <LazyCell<i32>>::lazy(root_key);
+            assert_eq!(cell2.get(), None);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn lazy_take_and_replace_work() -> ink_env::Result<()> {
+        run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let root_key = Key::from([0x42; 32]);
+            SpreadLayout::push_spread(&Lazy::new(10u32), &mut KeyPtr::from(root_key));
+            let mut lazy =
+                <Lazy<u32> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+            assert_eq!(Lazy::replace(&mut lazy, 20), 10);
+            assert_eq!(*lazy, 20);
+            assert_eq!(Lazy::take(&mut lazy), 20);
+            assert_eq!(*lazy, 0);
+            assert_eq!(Lazy::replace(&mut lazy, 30), 0);
+            SpreadLayout::push_spread(&lazy, &mut KeyPtr::from(root_key));
+            let lazy2 =
+                <Lazy<u32> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+            assert_eq!(*lazy2, 30);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn mutate_with_works() {
+        let mut cell = <LazyCell<i32>>::new(Some(1));
+        assert_eq!(
+            cell.mutate_with(|value| {
+                *value += 1;
+                *value
+            }),
+            Some(2)
+        );
+        assert_eq!(cell.get(), Some(&2));
+        // The closure is not called for empty cells.
+        let mut cell = <LazyCell<i32>>::new(None);
+        assert_eq!(cell.mutate_with(|_| panic!("must not be called")), None::<()>);
+        assert_eq!(cell.get(), None);
+    }
+
+    #[test]
+    fn mutate_with_only_marks_changed_values() -> ink_env::Result<()> {
+        run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let root_key = Key::from([0x42; 32]);
+            let cell = LazyCell::new(Some(1i32));
+            SpreadLayout::push_spread(&cell, &mut KeyPtr::from(root_key));
+            let mut cell =
+                <LazyCell<i32> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+            let contract = ink_env::test::get_current_contract_account_id::<
+                ink_env::DefaultEnvironment,
+            >()?;
+            let count_writes = || {
+                ink_env::test::get_contract_storage_rw::<ink_env::DefaultEnvironment>(
+                    &contract,
+                )
+                .map(|(_reads, writes)| writes)
+            };
+            // Leaving the value unchanged preserves the entry.
+            assert_eq!(cell.mutate_with(|value| *value), Some(1));
+            assert_eq!(
+                cell.entry(),
+                Some(&StorageEntry::new(Some(1), EntryState::Preserved))
+            );
+            let writes = count_writes()?;
+            SpreadLayout::push_spread(&cell, &mut KeyPtr::from(root_key));
+            assert_eq!(count_writes()?, writes);
+            // Changing the value marks the entry as mutated.
+            assert_eq!(cell.mutate_with(|value| *value += 1), Some(()));
+            assert_eq!(
+                cell.entry(),
+                Some(&StorageEntry::new(Some(2), EntryState::Mutated))
+            );
+            SpreadLayout::push_spread(&cell, &mut KeyPtr::from(root_key));
+            assert_eq!(count_writes()?, writes + 1);
+            let cell2 =
+                <LazyCell<i32> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+            assert_eq!(cell2.get(), Some(&2));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn set_if_changed_only_marks_changed_values() -> ink_env::Result<()> {
+        run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let root_key = Key::from([0x42; 32]);
+            let cell = LazyCell::new(Some(1i32));
+            SpreadLayout::push_spread(&cell, &mut KeyPtr::from(root_key));
+            let mut cell =
+                <LazyCell<i32> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+            assert_eq!(cell.get(), Some(&1));
+            let contract = ink_env::test::get_current_contract_account_id::<
+                ink_env::DefaultEnvironment,
+            >()?;
+            let count_writes = || {
+                ink_env::test::get_contract_storage_rw::<ink_env::DefaultEnvironment>(
+                    &contract,
+                )
+                .map(|(_reads, writes)| writes)
+            };
+            // Setting an equal value preserves the entry.
+            cell.set_if_changed(1);
+            assert_eq!(
+                cell.entry(),
+                Some(&StorageEntry::new(Some(1), EntryState::Preserved))
+            );
+            let writes = count_writes()?;
+            SpreadLayout::push_spread(&cell, &mut KeyPtr::from(root_key));
+            assert_eq!(count_writes()?, writes);
+            // Setting a different value marks the entry as mutated.
+            cell.set_if_changed(2);
+            assert_eq!(
+                cell.entry(),
+                Some(&StorageEntry::new(Some(2), EntryState::Mutated))
+            );
+            SpreadLayout::push_spread(&cell, &mut KeyPtr::from(root_key));
+            assert_eq!(count_writes()?, writes + 1);
+            let cell2 =
+                <LazyCell<i32> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+            assert_eq!(cell2.get(), Some(&2));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn mutate_with_does_not_mark_empty_cells() -> ink_env::Result<()> {
+        run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let root_key = Key::from([0x42; 32]);
+            let mut cell =
+                <LazyCell<i32> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+            assert_eq!(cell.mutate_with(|_| panic!("must not be called")), None::<()>);
+            assert_eq!(
+                cell.entry(),
+                Some(&StorageEntry::new(None, EntryState::Preserved))
+            );
+            Ok(())
+        })
+    }
+
     #[test]
     fn spread_layout_works() -> ink_env::Result<()> {
         run_test::<ink_env::DefaultEnvironment, _>(|_| {
@@ -472,6 +775,53 @@ mod tests {
         })
     }
 
+    #[test]
+    fn get_is_served_from_cache() -> ink_env::Result<()> {
+        run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let root_key = Key::from([0x42; 32]);
+            ink_env::set_contract_storage(&root_key, &1i32);
+            let cell = <LazyCell<i32>>::lazy(root_key);
+            assert_eq!(cell.get(), Some(&1));
+            // Changing the contract storage behind the back of the cell shows
+            // that subsequent reads do not load the value again.
+            ink_env::set_contract_storage(&root_key, &2i32);
+            assert_eq!(cell.get(), Some(&1));
+            // The same holds after setting a value without a prior read.
+            let mut cell = <LazyCell<i32>>::lazy(root_key);
+            cell.set(3);
+            ink_env::set_contract_storage(&root_key, &4i32);
+            assert_eq!(cell.get(), Some(&3));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn repeated_sets_are_written_once() -> ink_env::Result<()> {
+        run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let root_key = Key::from([0x42; 32]);
+            let mut cell = <LazyCell<i32>>::lazy(root_key);
+            for value in 0..10 {
+                cell.set(value);
+            }
+            // Nothing is written to the contract storage before the push.
+            assert_eq!(ink_env::get_contract_storage::<i32>(&root_key), Ok(None));
+            assert_eq!(
+                cell.entry(),
+                Some(&StorageEntry::new(Some(9), EntryState::Mutated))
+            );
+            SpreadLayout::push_spread(&cell, &mut KeyPtr::from(root_key));
+            assert_eq!(ink_env::get_contract_storage::<i32>(&root_key), Ok(Some(9)));
+            // Pulled cells that are only read are not written back.
+            let cell =
+                <LazyCell<i32> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+            assert_eq!(cell.get(), Some(&9));
+            ink_env::set_contract_storage(&root_key, &10i32);
+            SpreadLayout::push_spread(&cell, &mut KeyPtr::from(root_key));
+            assert_eq!(ink_env::get_contract_storage::<i32>(&root_key), Ok(Some(10)));
+            Ok(())
+        })
+    }
+
     #[test]
     fn lazy_set_works_with_spread_layout_push_pull() -> ink_env::Result<()> {
         run_test::<ink_env::DefaultEnvironment, _>(|_| {
@@ -676,6 +676,35 @@ where
         self.lazily_load_mut(index).value_mut().into()
     }
 
+    /// Returns `true` if there is a value associated with the given key.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`LazyHashMap::get`] this does not decode the value from the
+    /// contract storage if it has not been loaded, yet. Instead it only queries
+    /// whether the contract storage holds any value for the given key.
+    ///
+    /// # Panics
+    ///
+    /// - If the lazy hashmap is in an invalid state that forbids interaction.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Ord + scale::Encode + ToOwned<Owned = K>,
+    {
+        if let Some(entry) = self.entries().get(key) {
+            return entry.value().is_some()
+        }
+        self.key_at(key)
+            .map(|key| {
+                // Decoding into `()` never fails and does not touch the encoded value.
+                ink_env::get_contract_storage::<()>(&key)
+                    .expect("decoding into `()` cannot fail")
+                    .is_some()
+            })
+            .unwrap_or(false)
+    }
+
     /// Puts the new value under the given key and returns the old value if any.
     ///
     /// # Note
@@ -1077,6 +1106,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn key_at_works_for_composite_keys() {
+        /// A user defined composite key such as the allowances of ERC-20.
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord, scale::Encode)]
+        struct Allowance {
+            owner: [u8; 32],
+            spender: [u8; 32],
+        }
+
+        let key = Key::from([0x42; 32]);
+        let structs = <LazyHashMap<Allowance, u8, Blake2x256>>::lazy(key);
+        let tuples = <LazyHashMap<([u8; 32], [u8; 32]), u8, Blake2x256>>::lazy(key);
+        let allowance = Allowance {
+            owner: [0x01; 32],
+            spender: [0x02; 32],
+        };
+        // Deriving `scale::Encode` encodes the fields in declaration order
+        // so the struct is hashed the same way as its fields in a tuple.
+        assert_eq!(
+            structs.key_at(&allowance),
+            tuples.key_at(&([0x01; 32], [0x02; 32]))
+        );
+        assert_ne!(
+            structs.key_at(&allowance),
+            tuples.key_at(&([0x02; 32], [0x01; 32]))
+        );
+    }
+
+    #[test]
+    fn key_at_does_not_collide_for_variable_length_tuple_fields() {
+        use ink_prelude::vec::Vec;
+        let key = Key::from([0x42; 32]);
+        let hmap = <LazyHashMap<(Vec<u8>, Vec<u8>), u8, Blake2x256>>::lazy(key);
+        let ab_c = (b"ab".to_vec(), b"c".to_vec());
+        let a_bc = (b"a".to_vec(), b"bc".to_vec());
+        // The naive concatenations of both keys are equal.
+        assert_eq!([&ab_c.0[..], &ab_c.1[..]].concat(), b"abc".to_vec());
+        assert_eq!([&a_bc.0[..], &a_bc.1[..]].concat(), b"abc".to_vec());
+        // The SCALE encoding prefixes each variable length field with its length
+        // so that the keys are encoded and thus hashed differently.
+        assert_ne!(scale::Encode::encode(&ab_c), scale::Encode::encode(&a_bc));
+        assert_ne!(hmap.key_at(&ab_c), hmap.key_at(&a_bc));
+    }
+
     #[test]
     fn put_get_works() {
         let mut hmap = new_hmap();
@@ -1165,6 +1238,34 @@ mod tests {
         assert_eq!(hmap.get_mut(&5), None);
     }
 
+    #[test]
+    fn contains_key_works() -> ink_env::Result<()> {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut hmap = new_hmap();
+            assert!(!hmap.contains_key(&1));
+            assert_eq!(hmap.put_get(&1, Some(b'A')), None);
+            assert_eq!(hmap.put_get(&2, Some(b'B')), None);
+            assert!(hmap.contains_key(&1));
+            assert!(hmap.contains_key(&2));
+            assert!(!hmap.contains_key(&3));
+            let root_key = Key::from([0x42; 32]);
+            SpreadLayout::push_spread(&hmap, &mut KeyPtr::from(root_key));
+            let mut hmap2 =
+                <LazyHashMap<i32, u8, Blake2x256> as SpreadLayout>::pull_spread(
+                    &mut KeyPtr::from(root_key),
+                );
+            // Querying the contract storage does not load the values.
+            assert!(hmap2.contains_key(&1));
+            assert!(hmap2.contains_key(&2));
+            assert!(!hmap2.contains_key(&3));
+            assert_cached_entries(&hmap2, &[]);
+            // Removed values that have not yet been pushed are respected.
+            assert_eq!(hmap2.put_get(&1, None), Some(b'A'));
+            assert!(!hmap2.contains_key(&1));
+            Ok(())
+        })
+    }
+
     #[test]
     fn put_works() {
         let mut hmap = new_hmap();
@@ -33,6 +33,7 @@ use core::{
 use ink_prelude::{
     boxed::Box,
     collections::BTreeMap,
+    vec::Vec,
 };
 use ink_primitives::Key;
 
@@ -291,6 +292,38 @@ where
             ink_env::clear_contract_storage(&root_key);
         }
     }
+
+    /// Clears the `count` entries starting at index `start`.
+    ///
+    /// This clears the underlying contract storage of the entries right away
+    /// and caches them as being empty so that subsequent accesses to them do not
+    /// read from the contract storage. Indices beyond `Index::MAX` are ignored.
+    ///
+    /// # Panics
+    ///
+    /// If the entries require a deep clean-up and the decoding of one of them
+    /// failed.
+    pub fn clear_range(&mut self, start: Index, count: u32) {
+        if count == 0 {
+            return
+        }
+        let last = start.saturating_add(count - 1);
+        for index in start..=last {
+            if let Some(root_key) = self.key_at(index) {
+                if <V as SpreadLayout>::REQUIRES_DEEP_CLEAN_UP {
+                    if let Some(entity) = self.get(index) {
+                        clear_packed_root::<V>(entity, &root_key);
+                    }
+                } else {
+                    ink_env::clear_contract_storage(&root_key);
+                }
+            }
+            self.entries_mut().insert(
+                index,
+                Box::new(StorageEntry::new(None, EntryState::Preserved)),
+            );
+        }
+    }
 }
 
 impl<V> LazyIndexMap<V>
@@ -386,6 +419,28 @@ where
         unsafe { &*self.lazily_load(index).as_ptr() }.value().into()
     }
 
+    /// Returns shared references to the `count` elements starting at `start`.
+    ///
+    /// The returned vector yields `None` for every index without an element.
+    ///
+    /// # Note
+    ///
+    /// This loads all elements of the index range that are not yet cached
+    /// so subsequent accesses to them are served from the cache.
+    /// Indices beyond `Index::MAX` are ignored.
+    ///
+    /// # Panics
+    ///
+    /// - If the lazy chunk is in an invalid state that forbids interaction.
+    /// - If the decoding of an element within the range failed.
+    pub fn load_range(&self, start: Index, count: u32) -> Vec<Option<&V>> {
+        if count == 0 {
+            return Vec::new()
+        }
+        let last = start.saturating_add(count - 1);
+        (start..=last).map(|index| self.get(index)).collect()
+    }
+
     /// Returns an exclusive reference to the element at the given index if any.
     ///
     /// # Panics
@@ -697,6 +752,73 @@ mod tests {
         );
     }
 
+    #[test]
+    fn load_range_works() -> ink_env::Result<()> {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut imap = <LazyIndexMap<u8>>::new();
+            assert_eq!(imap.put_get(1, Some(b'A')), None);
+            assert_eq!(imap.put_get(2, Some(b'B')), None);
+            assert_eq!(imap.put_get(4, Some(b'D')), None);
+            let root_key = Key::from([0x42; 32]);
+            SpreadLayout::push_spread(&imap, &mut KeyPtr::from(root_key));
+            let imap2 = <LazyIndexMap<u8> as SpreadLayout>::pull_spread(
+                &mut KeyPtr::from(root_key),
+            );
+            assert_cached_entries(&imap2, &[]);
+            assert_eq!(
+                imap2.load_range(0, 6),
+                vec![None, Some(&b'A'), Some(&b'B'), None, Some(&b'D'), None]
+            );
+            // The whole range has been loaded into the cache.
+            assert_eq!(imap2.entries().len(), 6);
+            assert_eq!(
+                imap2.load_range(0, 6),
+                (0..6).map(|index| imap2.get(index)).collect::<Vec<_>>()
+            );
+            assert_eq!(imap2.load_range(2, 0), vec![]);
+            assert_eq!(imap2.load_range(Index::MAX, 3), vec![None]);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn clear_range_works() -> ink_env::Result<()> {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let mut imap = <LazyIndexMap<u8>>::new();
+            for (index, value) in (b'A'..=b'F').enumerate() {
+                imap.put(index as Index, Some(value));
+            }
+            let root_key = Key::from([0x42; 32]);
+            SpreadLayout::push_spread(&imap, &mut KeyPtr::from(root_key));
+            let mut imap2 = <LazyIndexMap<u8> as SpreadLayout>::pull_spread(
+                &mut KeyPtr::from(root_key),
+            );
+            imap2.clear_range(1, 3);
+            // The cleared entries are cached and need not be read again.
+            assert_cached_entries(
+                &imap2,
+                &[
+                    (1, StorageEntry::new(None, EntryState::Preserved)),
+                    (2, StorageEntry::new(None, EntryState::Preserved)),
+                    (3, StorageEntry::new(None, EntryState::Preserved)),
+                ],
+            );
+            let expected = vec![Some(&b'A'), None, None, None, Some(&b'E'), Some(&b'F')];
+            assert_eq!(imap2.load_range(0, 6), expected);
+            // The contract storage has been cleared even without pushing.
+            let imap3 = <LazyIndexMap<u8> as SpreadLayout>::pull_spread(
+                &mut KeyPtr::from(root_key),
+            );
+            assert_eq!(imap3.load_range(0, 6), expected);
+            // Clearing empty ranges or ranges at the end of the indices works.
+            imap2.clear_range(0, 0);
+            imap2.clear_range(Index::MAX, 3);
+            assert_eq!(imap2.get(0), Some(&b'A'));
+            assert_eq!(imap2.get(Index::MAX), None);
+            Ok(())
+        })
+    }
+
     #[test]
     fn spread_layout_works() -> ink_env::Result<()> {
         ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
@@ -159,6 +159,57 @@ where
         lazy.cell.get_mut().expect("encountered empty storage cell")
     }
 
+    /// Replaces the value with `new_value` and returns the old value.
+    ///
+    /// # Note
+    ///
+    /// This loads the value from the contract storage if this did not happen before.
+    /// Prefer [`Lazy::set`] in case the old value is of no interest to the caller.
+    ///
+    /// # Panics
+    ///
+    /// If loading from contract storage failed.
+    pub fn replace(lazy: &mut Self, new_value: T) -> T {
+        core::mem::replace(Lazy::get_mut(lazy), new_value)
+    }
+
+    /// Takes the value and leaves the default value in its place.
+    ///
+    /// # Note
+    ///
+    /// This loads the value from the contract storage if this did not happen before.
+    ///
+    /// # Panics
+    ///
+    /// If loading from contract storage failed.
+    pub fn take(lazy: &mut Self) -> T
+    where
+        T: Default,
+    {
+        Lazy::replace(lazy, Default::default())
+    }
+
+    /// Mutates the value through `f` and returns its result.
+    ///
+    /// # Note
+    ///
+    /// This loads the value from the contract storage if this did not happen before.
+    /// The value is only written back to the contract storage if `f` actually
+    /// changed it.
+    ///
+    /// # Panics
+    ///
+    /// If loading from contract storage failed.
+    pub fn mutate_with<F, R>(lazy: &mut Self, f: F) -> R
+    where
+        T: Clone + PartialEq,
+        F: FnOnce(&mut T) -> R,
+    {
+        lazy.cell
+            .mutate_with(f)
+            .expect("encountered empty storage cell")
+    }
+
     /// Sets the value to `value`, without executing any reads.
     ///
     /// # Note
@@ -175,6 +226,25 @@ where
     pub fn set(lazy: &mut Self, new_value: T) {
         lazy.cell.set(new_value);
     }
+
+    /// Sets the value to `value` unless it is equal to the cached value.
+    ///
+    /// # Note
+    ///
+    /// No reads from contract storage will be executed.
+    /// The value is only written back to the contract storage if it has
+    /// not been loaded before or actually differs from `new_value`.
+    ///
+    /// # Panics
+    ///
+    /// If accessing the inner value fails.
+    #[inline]
+    pub fn set_if_changed(lazy: &mut Self, new_value: T)
+    where
+        T: PartialEq,
+    {
+        lazy.cell.set_if_changed(new_value);
+    }
 }
 
 impl<T> From<T> for Lazy<T>
@@ -309,3 +379,97 @@ where
         Lazy::get_mut(self)
     }
 }
+
+impl<T> core::ops::AddAssign<T> for Lazy<T>
+where
+    T: SpreadLayout + core::ops::AddAssign<T>,
+{
+    /// Loads the value if necessary and adds `rhs` to it.
+    ///
+    /// The value is marked as mutated and written back upon the next flush.
+    fn add_assign(&mut self, rhs: T) {
+        *Lazy::get_mut(self) += rhs
+    }
+}
+
+impl<T> core::ops::SubAssign<T> for Lazy<T>
+where
+    T: SpreadLayout + core::ops::SubAssign<T>,
+{
+    /// Loads the value if necessary and subtracts `rhs` from it.
+    ///
+    /// The value is marked as mutated and written back upon the next flush.
+    fn sub_assign(&mut self, rhs: T) {
+        *Lazy::get_mut(self) -= rhs
+    }
+}
+
+impl<'a, T> core::ops::Add<T> for &'a Lazy<T>
+where
+    T: SpreadLayout,
+    &'a T: core::ops::Add<T, Output = T>,
+{
+    type Output = T;
+
+    /// Loads the value if necessary and returns the sum with `rhs`.
+    fn add(self, rhs: T) -> Self::Output {
+        Lazy::get(self) + rhs
+    }
+}
+
+impl<'a, T> core::ops::Sub<T> for &'a Lazy<T>
+where
+    T: SpreadLayout,
+    &'a T: core::ops::Sub<T, Output = T>,
+{
+    type Output = T;
+
+    /// Loads the value if necessary and returns the difference with `rhs`.
+    fn sub(self, rhs: T) -> Self::Output {
+        Lazy::get(self) - rhs
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Lazy;
+    use crate::traits::{
+        KeyPtr,
+        SpreadLayout,
+    };
+    use ink_primitives::Key;
+
+    #[test]
+    fn arithmetic_operators_work() -> ink_env::Result<()> {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let root_key = Key::from([0x42; 32]);
+            let lazy = Lazy::new(100u128);
+            SpreadLayout::push_spread(&lazy, &mut KeyPtr::from(root_key));
+            let contract = ink_env::test::get_current_contract_account_id::<
+                ink_env::DefaultEnvironment,
+            >()?;
+            let storage_rw = || {
+                ink_env::test::get_contract_storage_rw::<ink_env::DefaultEnvironment>(
+                    &contract,
+                )
+            };
+            let mut lazy =
+                <Lazy<u128> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+            // Computing a result with `Add` and `Sub` does not change the value.
+            assert_eq!(&lazy + 5, 105);
+            let (reads, writes) = storage_rw()?;
+            assert_eq!(&lazy - 5, 95);
+            assert_eq!(*lazy, 100);
+            lazy += 20;
+            lazy -= 5;
+            assert_eq!(*lazy, 115);
+            // Only the first operation loaded the value and flushing stores it once.
+            SpreadLayout::push_spread(&lazy, &mut KeyPtr::from(root_key));
+            assert_eq!(storage_rw()?, (reads, writes + 1));
+            let lazy =
+                <Lazy<u128> as SpreadLayout>::pull_spread(&mut KeyPtr::from(root_key));
+            assert_eq!(*lazy, 115);
+            Ok(())
+        })
+    }
+}
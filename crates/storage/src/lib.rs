@@ -43,6 +43,10 @@
     unused_extern_crates
 )]
 
+// Allows the storage derives to refer to `::ink_storage` in the unit tests.
+#[cfg(test)]
+extern crate self as ink_storage;
+
 #[cfg(all(test, feature = "std", feature = "ink-fuzz-tests"))]
 #[macro_use(quickcheck)]
 extern crate quickcheck_macros;
@@ -54,6 +58,8 @@ mod memory;
 mod pack;
 pub mod traits;
 
+#[cfg(all(test, feature = "std"))]
+mod derive_tests;
 #[cfg(test)]
 mod hashmap_entry_api_tests;
 
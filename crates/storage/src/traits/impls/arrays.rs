@@ -92,6 +92,13 @@ forward_supported_array_lens!(impl_layout_for_array);
 #[cfg(test)]
 mod tests {
     use crate::push_pull_works_for_primitive;
+    use crate::traits::{
+        pull_spread_root,
+        push_spread_root,
+        SpreadLayout,
+    };
+    use ink_env::get_contract_storage;
+    use ink_primitives::Key;
 
     type Array = [i32; 4];
     push_pull_works_for_primitive!(
@@ -115,4 +122,21 @@ mod tests {
             ]
         ]
     );
+
+    #[test]
+    fn array_spreads_elements_into_consecutive_cells() {
+        crate::test_utils::run_test(|| {
+            type Array = [(u8, i32); 3];
+            assert_eq!(<Array as SpreadLayout>::FOOTPRINT, 6);
+            let key = Key::from([0x42; 32]);
+            let array: Array = [(1, -1), (2, -2), (3, -3)];
+            push_spread_root(&array, &key);
+            for (n, (byte, int)) in array.iter().enumerate() {
+                let at = key + 2 * n as u64;
+                assert_eq!(get_contract_storage::<u8>(&at).unwrap(), Some(*byte));
+                assert_eq!(get_contract_storage::<i32>(&(at + 1)).unwrap(), Some(*int));
+            }
+            assert_eq!(pull_spread_root::<Array>(&key), array);
+        })
+    }
 }
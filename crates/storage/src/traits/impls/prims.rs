@@ -27,6 +27,7 @@ use ink_prelude::{
     string::String,
 };
 use ink_primitives::Key;
+use scale::Compact;
 
 macro_rules! impl_layout_for_primitive {
     ( $($ty:ty),* $(,)? ) => {
@@ -55,6 +56,14 @@ impl_layout_for_primitive!(
     i8, i16, i32, i64, i128,
 );
 
+// Compact encoded unsigned integers occupy fewer bytes in the contract storage
+// for small values, e.g. a `Compact<u128>` balance of `42` takes up a single
+// byte instead of 16 bytes.
+#[rustfmt::skip]
+impl_layout_for_primitive!(
+    Compact<u8>, Compact<u16>, Compact<u32>, Compact<u64>, Compact<u128>,
+);
+
 impl<T> SpreadLayout for Option<T>
 where
     T: SpreadLayout,
@@ -278,6 +287,28 @@ mod tests {
     type OptionU8 = Option<u8>;
     push_pull_works_for_primitive!(OptionU8, [Some(13u8), None]);
 
+    #[test]
+    fn option_spreads_discriminant_before_value() {
+        use crate::traits::{
+            push_spread_root,
+            SpreadLayout,
+        };
+        use ink_env::get_contract_storage;
+        crate::test_utils::run_test(|| {
+            assert_eq!(<Option<(u8, u16)> as SpreadLayout>::FOOTPRINT, 3);
+            let some = Key::from([0x42; 32]);
+            push_spread_root(&Some((13u8, 42u16)), &some);
+            assert_eq!(get_contract_storage::<u8>(&some).unwrap(), Some(1));
+            assert_eq!(get_contract_storage::<u8>(&(some + 1)).unwrap(), Some(13));
+            assert_eq!(get_contract_storage::<u16>(&(some + 2)).unwrap(), Some(42));
+            // A `None` only occupies its discriminant cell.
+            let none = Key::from([0x77; 32]);
+            push_spread_root(&<Option<(u8, u16)>>::None, &none);
+            assert_eq!(get_contract_storage::<u8>(&none).unwrap(), Some(0));
+            assert_eq!(get_contract_storage::<u8>(&(none + 1)).unwrap(), None);
+        })
+    }
+
     type ResultU8 = Result<u8, bool>;
     push_pull_works_for_primitive!(ResultU8, [Ok(13u8), Err(false)]);
 
@@ -286,4 +317,53 @@ mod tests {
 
     type BoxOptionU8 = Box<Option<u8>>;
     push_pull_works_for_primitive!(BoxOptionU8, [Box::new(Some(27)), Box::new(None)]);
+
+    type CompactU32 = scale::Compact<u32>;
+    push_pull_works_for_primitive!(
+        CompactU32,
+        [
+            scale::Compact(0),
+            scale::Compact(63),
+            scale::Compact(16_384),
+            scale::Compact(u32::MAX)
+        ]
+    );
+
+    type CompactU128 = scale::Compact<u128>;
+    push_pull_works_for_primitive!(
+        CompactU128,
+        [
+            scale::Compact(0),
+            scale::Compact(100),
+            scale::Compact(1_000_000),
+            scale::Compact(u128::MAX)
+        ]
+    );
+
+    #[test]
+    fn compact_values_are_stored_compactly() {
+        use crate::traits::{
+            pull_spread_root,
+            push_spread_root,
+        };
+        use ink_env::get_contract_storage;
+        use scale::Compact;
+        crate::test_utils::run_test(|| {
+            // A small value occupies a single byte instead of 16 bytes.
+            let small = Key::from([0x42; 32]);
+            push_spread_root(&Compact(42u128), &small);
+            assert_eq!(get_contract_storage::<[u8; 1]>(&small).unwrap(), Some([168]));
+            assert!(get_contract_storage::<[u8; 2]>(&small).is_err());
+            assert_eq!(pull_spread_root::<Compact<u128>>(&small), Compact(42u128));
+            // Big values use a length prefixed encoding.
+            let big = Key::from([0x77; 32]);
+            push_spread_root(&Compact(u32::MAX), &big);
+            assert_eq!(
+                get_contract_storage::<[u8; 5]>(&big).unwrap(),
+                Some([0x03, 0xFF, 0xFF, 0xFF, 0xFF])
+            );
+            assert!(get_contract_storage::<[u8; 6]>(&big).is_err());
+            assert_eq!(pull_spread_root::<Compact<u32>>(&big), Compact(u32::MAX));
+        })
+    }
 }
@@ -104,6 +104,13 @@ impl_layout_for_tuple!(A, B, C, D, E, F, G, H, I, J);
 #[cfg(test)]
 mod tests {
     use crate::push_pull_works_for_primitive;
+    use crate::traits::{
+        pull_spread_root,
+        push_spread_root,
+        SpreadLayout,
+    };
+    use ink_env::get_contract_storage;
+    use ink_primitives::Key;
 
     type TupleSix = (i32, u32, String, u8, bool, Box<Option<i32>>);
     push_pull_works_for_primitive!(
@@ -135,4 +142,37 @@ mod tests {
             )
         ]
     );
+
+    #[test]
+    fn tuple_spreads_elements_in_order() {
+        crate::test_utils::run_test(|| {
+            type Tuple = (u8, Option<u16>, (u32, u64));
+            assert_eq!(<Tuple as SpreadLayout>::FOOTPRINT, 5);
+            let key = Key::from([0x42; 32]);
+            let tuple: Tuple = (1, Some(2), (3, 4));
+            push_spread_root(&tuple, &key);
+            assert_eq!(get_contract_storage::<u8>(&key).unwrap(), Some(1));
+            assert_eq!(get_contract_storage::<u8>(&(key + 1)).unwrap(), Some(1));
+            assert_eq!(get_contract_storage::<u16>(&(key + 2)).unwrap(), Some(2));
+            assert_eq!(get_contract_storage::<u32>(&(key + 3)).unwrap(), Some(3));
+            assert_eq!(get_contract_storage::<u64>(&(key + 4)).unwrap(), Some(4));
+            assert_eq!(pull_spread_root::<Tuple>(&key), tuple);
+        })
+    }
+
+    #[test]
+    fn largest_tuple_spreads_elements_in_order() {
+        crate::test_utils::run_test(|| {
+            type Tuple = (u8, u8, u8, u8, u8, u8, u8, u8, u8, u8);
+            assert_eq!(<Tuple as SpreadLayout>::FOOTPRINT, 10);
+            let key = Key::from([0x42; 32]);
+            let tuple: Tuple = (0, 1, 2, 3, 4, 5, 6, 7, 8, 9);
+            push_spread_root(&tuple, &key);
+            for n in 0..10 {
+                let cell = get_contract_storage::<u8>(&(key + n)).unwrap();
+                assert_eq!(cell, Some(n as u8));
+            }
+            assert_eq!(pull_spread_root::<Tuple>(&key), tuple);
+        })
+    }
 }
@@ -60,6 +60,17 @@ impl_storage_layout_for_primitives!(
     i8, i16, i32, i64, i128,
 );
 
+// The type metadata cannot express compact encodings, yet. Therefore a compact
+// encoded cell is described by the type of the integer it encodes.
+impl<T> StorageLayout for scale::Compact<T>
+where
+    T: TypeInfo + 'static,
+{
+    fn layout(key_ptr: &mut KeyPtr) -> Layout {
+        Layout::Cell(CellLayout::new::<T>(LayoutKey::from(key_ptr.advance_by(1))))
+    }
+}
+
 macro_rules! impl_storage_layout_for_arrays {
     ( $($len:literal),* $(,)? ) => {
         $(
@@ -113,6 +113,9 @@ where
 ///   spread layout.
 /// - Users should prefer using this function directly instead of using the
 ///   trait methods on [`SpreadLayout`].
+/// - Lazy storage abstractions only write the cells that have been mutated.
+///   Every written cell results in its own host call since the contracts
+///   pallet does not provide a batched storage API.
 pub fn push_spread_root<T>(entity: &T, root_key: &Key)
 where
     T: SpreadLayout,
@@ -136,11 +139,36 @@ pub fn pull_packed_root<T>(root_key: &Key) -> T
 where
     T: PackedLayout,
 {
-    let mut entity = ink_env::get_contract_storage::<T>(root_key)
+    try_pull_packed_root::<T>(root_key)
         .expect("could not properly decode storage entry")
-        .expect("storage entry was empty");
-    <T as PackedLayout>::pull_packed(&mut entity, root_key);
-    entity
+        .expect("storage entry was empty")
+}
+
+/// Tries to pull an instance of type `T` from the contract storage using packed layout.
+///
+/// The root key denotes the offset into the contract storage where the
+/// instance of type `T` is being pulled from.
+///
+/// Returns `Ok(None)` if the contract storage entry is empty.
+///
+/// # Note
+///
+/// - Other than [`pull_packed_root`] this does not panic if the storage entry
+///   is empty or has not been encoded as `T`, e.g. because it has been written
+///   by a contract using an older storage layout.
+///
+/// # Errors
+///
+/// - If the contract storage entry cannot be decoded as `T`.
+pub fn try_pull_packed_root<T>(root_key: &Key) -> ink_env::Result<Option<T>>
+where
+    T: PackedLayout,
+{
+    let entity = ink_env::get_contract_storage::<T>(root_key)?.map(|mut entity| {
+        <T as PackedLayout>::pull_packed(&mut entity, root_key);
+        entity
+    });
+    Ok(entity)
 }
 
 /// Pushes the entity to the contract storage using packed layout.
@@ -180,3 +208,30 @@ where
     <T as PackedLayout>::clear_packed(entity, root_key);
     ink_env::clear_contract_storage(root_key);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        pull_packed_root,
+        try_pull_packed_root,
+    };
+    use ink_primitives::Key;
+
+    #[test]
+    fn try_pull_packed_root_distinguishes_empty_and_malformed() {
+        ink_env::test::run_test::<ink_env::DefaultEnvironment, _>(|_| {
+            let root_key = Key::from([0x42; 32]);
+            // An empty storage entry is not an error.
+            assert_eq!(try_pull_packed_root::<u32>(&root_key), Ok(None));
+            // A storage entry that has been written using another type.
+            ink_env::set_contract_storage(&root_key, &0x05_u8);
+            assert!(try_pull_packed_root::<u32>(&root_key).is_err());
+            let result = std::panic::catch_unwind(|| pull_packed_root::<u32>(&root_key));
+            assert!(result.is_err());
+            // The storage entry can still be read using its actual type.
+            assert_eq!(try_pull_packed_root::<u8>(&root_key), Ok(Some(0x05)));
+            Ok(())
+        })
+        .unwrap()
+    }
+}
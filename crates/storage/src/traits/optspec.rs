@@ -101,17 +101,10 @@ pub fn pull_packed_root_opt<T>(root_key: &Key) -> Option<T>
 where
     T: PackedLayout,
 {
-    match ink_env::get_contract_storage::<T>(root_key)
+    // In case the contract storage is occupied we handle
+    // the Option<T> as if it was a T.
+    super::try_pull_packed_root::<T>(root_key)
         .expect("decoding does not match expected type")
-    {
-        Some(mut value) => {
-            // In case the contract storage is occupied we handle
-            // the Option<T> as if it was a T.
-            <T as PackedLayout>::pull_packed(&mut value, root_key);
-            Some(value)
-        }
-        None => None,
-    }
 }
 
 pub fn push_packed_root_opt<T>(entity: Option<&T>, root_key: &Key)
@@ -0,0 +1,269 @@
+// Copyright 2018-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink_lang as ink;
+
+#[ink::contract]
+mod code_registry {
+    use ink_env::call::{
+        build_create,
+        ExecutionInput,
+        FromAccountId,
+        Selector,
+    };
+    use ink_prelude::string::String;
+    #[cfg(not(feature = "ink-as-dependency"))]
+    use ink_storage::{
+        collections::hashmap::Entry,
+        collections::HashMap as StorageHashMap,
+        lazy::Lazy,
+    };
+
+    /// Emitted whenever a new code hash is registered under a name.
+    #[ink(event)]
+    pub struct Register {
+        name: String,
+        #[ink(topic)]
+        code_hash: Hash,
+    }
+
+    /// Emitted whenever a contract has been instantiated from a registered code hash.
+    #[ink(event)]
+    pub struct Instantiate {
+        name: String,
+        #[ink(topic)]
+        code_hash: Hash,
+        #[ink(topic)]
+        contract: AccountId,
+    }
+
+    /// A factory contract that maps human readable names to code hashes
+    /// of already uploaded contract code.
+    ///
+    /// # Description
+    ///
+    /// The owner of the registry registers code hashes under names.
+    /// Afterwards anyone can look up the code hash for a name or let the
+    /// registry instantiate a new contract from the code registered under it.
+    #[ink(storage)]
+    pub struct CodeRegistry {
+        /// The owner of the registry who is allowed to register code hashes.
+        owner: Lazy<AccountId>,
+        /// All registered names to code hashes mappings.
+        code_hashes: StorageHashMap<String, Hash>,
+    }
+
+    /// Errors that can occur upon calling this contract.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(::scale_info::TypeInfo))]
+    pub enum Error {
+        /// Returned if the name already has a code hash registered.
+        NameAlreadyExists,
+        /// Returned if no code hash is registered under the name.
+        UnknownName,
+        /// Returned if caller is not owner while required to.
+        CallerIsNotOwner,
+        /// Returned if the instantiation of the contract failed.
+        InstantiationFailed,
+    }
+
+    /// Type alias for the contract's result type.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// The account ID of a contract instantiated by the registry.
+    struct Instantiated(AccountId);
+
+    impl FromAccountId<Environment> for Instantiated {
+        fn from_account_id(account_id: AccountId) -> Self {
+            Self(account_id)
+        }
+    }
+
+    impl CodeRegistry {
+        /// Creates a new empty code registry owned by the caller.
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {
+                owner: Lazy::new(Self::env().caller()),
+                code_hashes: StorageHashMap::new(),
+            }
+        }
+
+        /// Registers the code hash under the given name.
+        ///
+        /// # Errors
+        ///
+        /// - If the caller is not the owner of the registry.
+        /// - If the name already has a code hash registered.
+        #[ink(message)]
+        pub fn register(&mut self, name: String, code_hash: Hash) -> Result<()> {
+            if self.env().caller() != *self.owner {
+                return Err(Error::CallerIsNotOwner)
+            }
+            match self.code_hashes.entry(name.clone()) {
+                Entry::Occupied(_) => return Err(Error::NameAlreadyExists),
+                Entry::Vacant(vacant) => {
+                    vacant.insert(code_hash);
+                }
+            }
+            self.env().emit_event(Register { name, code_hash });
+            Ok(())
+        }
+
+        /// Returns the code hash registered under the given name if any.
+        #[ink(message)]
+        pub fn code_hash(&self, name: String) -> Option<Hash> {
+            self.code_hashes.get(&name).copied()
+        }
+
+        /// Instantiates a new contract from the code registered under the given name.
+        ///
+        /// The new contract is instantiated by calling the constructor with the
+        /// given selector without any arguments and the given endowment.
+        ///
+        /// Returns the account ID of the instantiated contract.
+        ///
+        /// # Errors
+        ///
+        /// - If no code hash is registered under the name.
+        /// - If the instantiation of the contract failed.
+        #[ink(message)]
+        pub fn instantiate(
+            &mut self,
+            name: String,
+            constructor: [u8; 4],
+            endowment: Balance,
+        ) -> Result<AccountId> {
+            let code_hash = self.code_hash(name.clone()).ok_or(Error::UnknownName)?;
+            let Instantiated(contract) = build_create::<Environment, Instantiated>()
+                .code_hash(code_hash)
+                .endowment(endowment)
+                .exec_input(ExecutionInput::new(Selector::new(constructor)))
+                .instantiate()
+                .map_err(|_| Error::InstantiationFailed)?;
+            self.env().emit_event(Instantiate {
+                name,
+                code_hash,
+                contract,
+            });
+            Ok(contract)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink_lang as ink;
+
+        const DEFAULT_CALLEE_HASH: [u8; 32] = [0x07; 32];
+        const DEFAULT_ENDOWMENT: Balance = 1_000_000;
+        const DEFAULT_GAS_LIMIT: u64 = 1_000_000;
+
+        fn default_accounts(
+        ) -> ink_env::test::DefaultAccounts<ink_env::DefaultEnvironment> {
+            ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                .expect("off-chain environment should have been initialized already")
+        }
+
+        fn set_next_caller(caller: AccountId) {
+            ink_env::test::push_execution_context::<ink_env::DefaultEnvironment>(
+                caller,
+                AccountId::from(DEFAULT_CALLEE_HASH),
+                DEFAULT_GAS_LIMIT.into(),
+                DEFAULT_ENDOWMENT,
+                ink_env::test::CallData::new(ink_env::call::Selector::new([0x00; 4])),
+            )
+        }
+
+        #[ink::test]
+        fn register_works() {
+            let flipper = Hash::from([0x01; 32]);
+            let erc20 = Hash::from([0x02; 32]);
+
+            let mut registry = CodeRegistry::new();
+            assert_eq!(registry.code_hash(String::from("flipper")), None);
+
+            assert_eq!(registry.register(String::from("flipper"), flipper), Ok(()));
+            assert_eq!(registry.register(String::from("erc20"), erc20), Ok(()));
+            assert_eq!(registry.code_hash(String::from("flipper")), Some(flipper));
+            assert_eq!(registry.code_hash(String::from("erc20")), Some(erc20));
+
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 2);
+        }
+
+        #[ink::test]
+        fn register_existing_name_fails() {
+            let mut registry = CodeRegistry::new();
+            assert_eq!(
+                registry.register(String::from("flipper"), Hash::from([0x01; 32])),
+                Ok(())
+            );
+            assert_eq!(
+                registry.register(String::from("flipper"), Hash::from([0x02; 32])),
+                Err(Error::NameAlreadyExists)
+            );
+            // The originally registered code hash is kept.
+            assert_eq!(
+                registry.code_hash(String::from("flipper")),
+                Some(Hash::from([0x01; 32]))
+            );
+        }
+
+        #[ink::test]
+        fn register_by_non_owner_fails() {
+            let accounts = default_accounts();
+
+            set_next_caller(accounts.alice);
+            let mut registry = CodeRegistry::new();
+
+            set_next_caller(accounts.bob);
+            assert_eq!(
+                registry.register(String::from("flipper"), Hash::from([0x01; 32])),
+                Err(Error::CallerIsNotOwner)
+            );
+            assert_eq!(registry.code_hash(String::from("flipper")), None);
+        }
+
+        #[ink::test]
+        fn instantiate_works() {
+            let flipper = Hash::from([0x01; 32]);
+            let mut registry = CodeRegistry::new();
+            assert_eq!(registry.register(String::from("flipper"), flipper), Ok(()));
+
+            let first = registry
+                .instantiate(String::from("flipper"), [0x00; 4], 0)
+                .expect("instantiating a registered contract must work");
+            let second = registry
+                .instantiate(String::from("flipper"), [0x00; 4], 0)
+                .expect("instantiating a registered contract must work");
+            // Every instantiation yields a new contract.
+            assert_ne!(first, second);
+
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 3);
+        }
+
+        #[ink::test]
+        fn instantiate_unknown_name_fails() {
+            let mut registry = CodeRegistry::new();
+            assert_eq!(
+                registry.instantiate(String::from("flipper"), [0x00; 4], 0),
+                Err(Error::UnknownName)
+            );
+        }
+    }
+}
@@ -34,6 +34,8 @@ mod erc20 {
         /// Mapping of the token amount which an account is allowed to withdraw
         /// from another account.
         allowances: StorageHashMap<(AccountId, AccountId), Balance>,
+        /// The account that instantiated the contract and is allowed to mint tokens.
+        owner: Lazy<AccountId>,
     }
 
     /// Event emitted when a token transfer occurs.
@@ -67,6 +69,10 @@ mod erc20 {
         InsufficientBalance,
         /// Returned if not enough allowance to fulfill a request is available.
         InsufficientAllowance,
+        /// Returned if the caller is not the owner while required to.
+        CallerIsNotOwner,
+        /// Returned if minting would overflow the total token supply.
+        Overflow,
     }
 
     /// The ERC-20 result type.
@@ -83,6 +89,7 @@ mod erc20 {
                 total_supply: Lazy::new(initial_supply),
                 balances,
                 allowances: StorageHashMap::new(),
+                owner: Lazy::new(caller),
             };
             Self::env().emit_event(Transfer {
                 from: None,
@@ -146,6 +153,44 @@ mod erc20 {
             Ok(())
         }
 
+        /// Increases the allowance of `spender` to withdraw from the caller's account
+        /// by `added_value`.
+        ///
+        /// Unlike `approve` this adjusts the current allowance instead of overwriting
+        /// it which avoids racing against a `spender` that withdraws in between.
+        /// The allowance saturates at the maximum balance.
+        ///
+        /// An `Approval` event with the new allowance is emitted.
+        #[ink(message)]
+        pub fn increase_allowance(
+            &mut self,
+            spender: AccountId,
+            added_value: Balance,
+        ) -> Result<()> {
+            let owner = self.env().caller();
+            let allowance = self.allowance(owner, spender);
+            self.approve(spender, allowance.saturating_add(added_value))
+        }
+
+        /// Decreases the allowance of `spender` to withdraw from the caller's account
+        /// by `subtracted_value`.
+        ///
+        /// Unlike `approve` this adjusts the current allowance instead of overwriting
+        /// it which avoids racing against a `spender` that withdraws in between.
+        /// The allowance is clamped at zero.
+        ///
+        /// An `Approval` event with the new allowance is emitted.
+        #[ink(message)]
+        pub fn decrease_allowance(
+            &mut self,
+            spender: AccountId,
+            subtracted_value: Balance,
+        ) -> Result<()> {
+            let owner = self.env().caller();
+            let allowance = self.allowance(owner, spender);
+            self.approve(spender, allowance.saturating_sub(subtracted_value))
+        }
+
         /// Transfers `value` tokens on the behalf of `from` to the account `to`.
         ///
         /// This can be used to allow a contract to transfer tokens on ones behalf and/or
@@ -177,6 +222,63 @@ mod erc20 {
             Ok(())
         }
 
+        /// Creates `value` new tokens and assigns them to the account `to`.
+        ///
+        /// Increases the total supply accordingly.
+        ///
+        /// On success a `Transfer` event without a sender is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `CallerIsNotOwner` error if the caller is not the owner of the
+        /// contract.
+        ///
+        /// Returns `Overflow` error if the total supply would overflow.
+        #[ink(message)]
+        pub fn mint(&mut self, to: AccountId, value: Balance) -> Result<()> {
+            if self.env().caller() != *self.owner {
+                return Err(Error::CallerIsNotOwner)
+            }
+            let total_supply =
+                self.total_supply.checked_add(value).ok_or(Error::Overflow)?;
+            *self.total_supply = total_supply;
+            // Cannot overflow since no balance exceeds the total supply.
+            *self.balances.entry(to).or_insert(0) += value;
+            self.env().emit_event(Transfer {
+                from: None,
+                to: Some(to),
+                value,
+            });
+            Ok(())
+        }
+
+        /// Destroys `value` tokens of the caller's account.
+        ///
+        /// Decreases the total supply accordingly.
+        ///
+        /// On success a `Transfer` event without a receiver is emitted.
+        ///
+        /// # Errors
+        ///
+        /// Returns `InsufficientBalance` error if there are not enough tokens on
+        /// the caller's account balance.
+        #[ink(message)]
+        pub fn burn(&mut self, value: Balance) -> Result<()> {
+            let from = self.env().caller();
+            let from_balance = self.balance_of(from);
+            if from_balance < value {
+                return Err(Error::InsufficientBalance)
+            }
+            self.balances.insert(from, from_balance - value);
+            *self.total_supply -= value;
+            self.env().emit_event(Transfer {
+                from: Some(from),
+                to: None,
+                value,
+            });
+            Ok(())
+        }
+
         /// Transfers `value` amount of tokens from the caller's account to account `to`.
         ///
         /// On success a `Transfer` event is emitted.
@@ -196,8 +298,7 @@ mod erc20 {
                 return Err(Error::InsufficientBalance)
             }
             self.balances.insert(from, from_balance - value);
-            let to_balance = self.balance_of(to);
-            self.balances.insert(to, to_balance + value);
+            *self.balances.entry(to).or_insert(0) += value;
             self.env().emit_event(Transfer {
                 from: Some(from),
                 to: Some(to),
@@ -411,6 +512,34 @@ mod erc20 {
             );
         }
 
+        #[ink::test]
+        fn transfer_exceeding_balance_leaves_state_unchanged() {
+            let mut erc20 = Erc20::new(100);
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+            assert_eq!(erc20.transfer(accounts.bob, 10), Ok(()));
+
+            // Alice fails to transfer one token more than she owns.
+            assert_eq!(
+                erc20.transfer(accounts.bob, 91),
+                Err(Error::InsufficientBalance)
+            );
+            // Bob fails to transfer the maximum balance.
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob)
+                .expect("Cannot set caller");
+            assert_eq!(
+                erc20.transfer(accounts.alice, Balance::MAX),
+                Err(Error::InsufficientBalance)
+            );
+            // Balances are unchanged and the total supply is preserved.
+            assert_eq!(erc20.balance_of(accounts.alice), 90);
+            assert_eq!(erc20.balance_of(accounts.bob), 10);
+            assert_eq!(erc20.total_supply(), 100);
+            // Only the construction and the first transfer emitted events.
+            assert_eq!(ink_env::test::recorded_events().count(), 2);
+        }
+
         #[ink::test]
         fn transfer_from_works() {
             // Constructor works.
@@ -473,6 +602,119 @@ mod erc20 {
             );
         }
 
+        #[ink::test]
+        fn approve_emits_approval_event() {
+            let mut erc20 = Erc20::new(100);
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+
+            // Alice approves Bob for token transfers on her behalf.
+            assert_eq!(erc20.approve(accounts.bob, 42), Ok(()));
+
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 2);
+            let approval_event = &emitted_events[1];
+            let decoded_event =
+                <Event as scale::Decode>::decode(&mut &approval_event.data[..])
+                    .expect("encountered invalid contract event data buffer");
+            if let Event::Approval(Approval {
+                owner,
+                spender,
+                value,
+            }) = decoded_event
+            {
+                assert_eq!(owner, accounts.alice, "encountered invalid Approval.owner");
+                assert_eq!(spender, accounts.bob, "encountered invalid Approval.spender");
+                assert_eq!(value, 42, "encountered invalid Approval.value");
+            } else {
+                panic!("encountered unexpected event kind: expected an Approval event")
+            }
+            // The event signature topic followed by one topic per field.
+            assert_eq!(approval_event.topics.len(), 4);
+        }
+
+        #[ink::test]
+        fn increase_and_decrease_allowance_works() {
+            let mut erc20 = Erc20::new(100);
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+
+            // Alice increases the allowance of Bob starting from zero.
+            assert_eq!(erc20.increase_allowance(accounts.bob, 10), Ok(()));
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 10);
+            assert_eq!(erc20.increase_allowance(accounts.bob, 5), Ok(()));
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), 15);
+            // Increasing saturates at the maximum balance.
+            assert_eq!(erc20.increase_allowance(accounts.bob, Balance::MAX), Ok(()));
+            assert_eq!(erc20.allowance(accounts.alice, accounts.bob), Balance::MAX);
+
+            // Alice decreases the allowance of Eve which is clamped at zero.
+            assert_eq!(erc20.approve(accounts.eve, 20), Ok(()));
+            assert_eq!(erc20.decrease_allowance(accounts.eve, 5), Ok(()));
+            assert_eq!(erc20.allowance(accounts.alice, accounts.eve), 15);
+            assert_eq!(erc20.decrease_allowance(accounts.eve, 100), Ok(()));
+            assert_eq!(erc20.allowance(accounts.alice, accounts.eve), 0);
+
+            // Every adjustment emitted an `Approval` event.
+            assert_eq!(ink_env::test::recorded_events().count(), 7);
+        }
+
+        #[ink::test]
+        fn mint_and_burn_track_total_supply() {
+            let mut erc20 = Erc20::new(100);
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+
+            // Alice as the owner mints 50 tokens for Bob.
+            assert_eq!(erc20.mint(accounts.bob, 50), Ok(()));
+            assert_eq!(erc20.balance_of(accounts.bob), 50);
+            assert_eq!(erc20.total_supply(), 150);
+            // Minting beyond the maximum supply fails.
+            assert_eq!(erc20.mint(accounts.bob, Balance::MAX), Err(Error::Overflow));
+            assert_eq!(erc20.total_supply(), 150);
+
+            // Bob burns 20 of his tokens but cannot burn more than he owns.
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob)
+                .expect("Cannot set caller");
+            assert_eq!(erc20.burn(20), Ok(()));
+            assert_eq!(erc20.burn(31), Err(Error::InsufficientBalance));
+            assert_eq!(erc20.balance_of(accounts.bob), 30);
+            assert_eq!(erc20.total_supply(), 130);
+            // Bob is not the owner and thus not allowed to mint.
+            assert_eq!(erc20.mint(accounts.bob, 10), Err(Error::CallerIsNotOwner));
+
+            let emitted_events = ink_env::test::recorded_events().collect::<Vec<_>>();
+            assert_eq!(emitted_events.len(), 3);
+            assert_transfer_event(&emitted_events[1], None, Some(accounts.bob), 50);
+            assert_transfer_event(&emitted_events[2], Some(accounts.bob), None, 20);
+        }
+
+        #[ink::test]
+        fn transfers_by_different_callers_work() {
+            let mut erc20 = Erc20::new(100);
+            let accounts =
+                ink_env::test::default_accounts::<ink_env::DefaultEnvironment>()
+                    .expect("Cannot get accounts");
+
+            // Alice transfers 30 tokens to Bob.
+            assert_eq!(erc20.transfer(accounts.bob, 30), Ok(()));
+            // Bob transfers 10 of his tokens to Eve.
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.bob)
+                .expect("Cannot set caller");
+            assert_eq!(erc20.transfer(accounts.eve, 10), Ok(()));
+            // Alice transfers 5 tokens to Eve.
+            ink_env::test::set_caller::<ink_env::DefaultEnvironment>(accounts.alice)
+                .expect("Cannot set caller");
+            assert_eq!(erc20.transfer(accounts.eve, 5), Ok(()));
+
+            assert_eq!(erc20.balance_of(accounts.alice), 65);
+            assert_eq!(erc20.balance_of(accounts.bob), 20);
+            assert_eq!(erc20.balance_of(accounts.eve), 15);
+        }
+
         #[ink::test]
         fn allowance_must_not_change_on_failed_transfer() {
             let mut erc20 = Erc20::new(100);
@@ -17,19 +17,20 @@
 
 declare -A results
 
-all_crates=("core" "alloc" "utils" "model" "lang" "lang" "lang/macro" "cli")
-wasm_crates=("core" "alloc" "utils" "model" "lang" "lang" "lang/macro")
+all_crates=("env" "storage" "storage/derive" "allocator" "prelude" "primitives" "lang" "lang/macro" "lang/ir")
+wasm_crates=("env" "storage" "allocator" "prelude" "primitives" "lang")
 
 results["check_all_features"]=true
 for crate in "${all_crates[@]}"; do
-    cargo check --verbose --all-features --manifest-path $crate/Cargo.toml
+    cargo check --verbose --all-features --manifest-path crates/$crate/Cargo.toml
     let "results['check_all_features'] |= $?"
-    cargo check --verbose --no-default-features --manifest-path $crate/Cargo.toml
+    # The `no_std` build is only supported for the Wasm target.
+    cargo check --verbose --no-default-features --target=wasm32-unknown-unknown --manifest-path crates/$crate/Cargo.toml
     let "results['check_no_defaults'] |= $?"
 done
 
 for crate in "${wasm_crates[@]}"; do
-    cargo build --verbose --manifest-path $crate/Cargo.toml --no-default-features --release --target=wasm32-unknown-unknown
+    cargo build --verbose --manifest-path crates/$crate/Cargo.toml --no-default-features --release --target=wasm32-unknown-unknown
     let "results['build_wasm'] |= $?"
 done
 
@@ -39,7 +40,7 @@ results["fmt"]=$?
 cargo clippy --verbose --all --all-features -- -D warnings
 results["clippy_all_features"]=$?
 
-cargo clippy --verbose --all --no-default-features -- -D warnings
+cargo clippy --verbose --all --no-default-features --target=wasm32-unknown-unknown -- -D warnings
 results["clippy_no_defaults"]=$?
 
 cargo test --verbose --all --all-features --release